@@ -0,0 +1,908 @@
+//! An ordered counter backed by a [`BTreeMap`].
+//!
+//! [`BTreeMap`]: https://doc.rust-lang.org/stable/std/collections/struct.BTreeMap.html
+
+use crate::{k_smallest, Counter};
+
+use num_traits::{One, Zero};
+
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::hash::{BuildHasher, Hash};
+use std::iter;
+use std::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, Deref, DerefMut, Index, IndexMut,
+    RangeBounds, Sub, SubAssign,
+};
+
+type BTreeCounterMap<T, N> = BTreeMap<T, N>;
+
+/// A counter which keeps its items in sorted order.
+///
+/// `BTreeCounter<T, N>` has the same interface as [`Counter`], but it is backed by a [`BTreeMap`]
+/// instead of a [`HashMap`]. Iteration therefore always proceeds in increasing order of the
+/// keys, and the counts of any range of keys can be queried with [`range_total()`].
+///
+/// [`BTreeMap`]: https://doc.rust-lang.org/stable/std/collections/struct.BTreeMap.html
+/// [`HashMap`]: https://doc.rust-lang.org/stable/std/collections/struct.HashMap.html
+/// [`range_total()`]: BTreeCounter::range_total
+///
+/// ```rust
+/// # use counter::BTreeCounter;
+/// let counter = "abracadabra".chars().collect::<BTreeCounter<_>>();
+/// let items = counter.into_iter().collect::<Vec<_>>();
+/// assert_eq!(items, vec![('a', 5), ('b', 2), ('c', 1), ('d', 1), ('r', 2)]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BTreeCounter<T: Ord, N = usize> {
+    map: BTreeCounterMap<T, N>,
+    // necessary for `Index::index` since we cannot declare generic `static` variables.
+    zero: N,
+}
+
+impl<T, N> BTreeCounter<T, N>
+where
+    T: Ord,
+{
+    /// Consumes this counter and returns a [`BTreeMap`] mapping the items to the counts.
+    ///
+    /// [`BTreeMap`]: https://doc.rust-lang.org/stable/std/collections/struct.BTreeMap.html
+    pub fn into_map(self) -> BTreeMap<T, N> {
+        self.map
+    }
+
+    /// Returns the sum of the counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use counter::BTreeCounter;
+    /// let counter = BTreeCounter::init("abracadabra".chars());
+    /// assert_eq!(counter.total::<usize>(), 11);
+    /// assert_eq!(counter.len(), 5);
+    /// ```
+    pub fn total<'a, R>(&'a self) -> R
+    where
+        R: iter::Sum<&'a N>,
+    {
+        self.map.values().sum()
+    }
+
+    /// Returns the sum of the counts of all items whose keys lie within `range`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use counter::BTreeCounter;
+    /// let counter = BTreeCounter::init("abracadabra".chars());
+    /// assert_eq!(counter.range_total::<_, _, usize>('b'..='d'), 4);
+    /// assert_eq!(counter.range_total::<_, _, usize>('e'..), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`BTreeMap::range`].
+    ///
+    /// [`BTreeMap::range`]: https://doc.rust-lang.org/stable/std/collections/struct.BTreeMap.html#method.range
+    pub fn range_total<'a, Q, B, R>(&'a self, range: B) -> R
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        B: RangeBounds<Q>,
+        R: iter::Sum<&'a N>,
+    {
+        self.map.range(range).map(|(_, count)| count).sum()
+    }
+}
+
+impl<T, N> BTreeCounter<T, N>
+where
+    T: Ord,
+    N: Zero,
+{
+    /// Create a new, empty `BTreeCounter`
+    pub fn new() -> BTreeCounter<T, N> {
+        BTreeCounter {
+            map: BTreeMap::new(),
+            zero: N::zero(),
+        }
+    }
+}
+
+impl<T, N> BTreeCounter<T, N>
+where
+    T: Ord,
+    N: AddAssign + Zero + One,
+{
+    /// Create a new `BTreeCounter` initialized with the given iterable.
+    pub fn init<I>(iterable: I) -> BTreeCounter<T, N>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut counter = BTreeCounter::new();
+        counter.update(iterable);
+        counter
+    }
+
+    /// Add the counts of the elements from the given iterable to this counter.
+    pub fn update<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            let entry = self.map.entry(item).or_insert_with(N::zero);
+            *entry += N::one();
+        }
+    }
+}
+
+impl<T, N> BTreeCounter<T, N>
+where
+    T: Ord,
+    N: PartialOrd + SubAssign + Zero + One,
+{
+    /// Remove the counts of the elements from the given iterable to this counter.
+    ///
+    /// Non-positive counts are automatically removed.
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// # use std::collections::BTreeMap;
+    /// let mut counter = "abbccc".chars().collect::<BTreeCounter<_>>();
+    /// counter.subtract("abba".chars());
+    /// let expect = [('c', 3)].iter().cloned().collect::<BTreeMap<_, _>>();
+    /// assert_eq!(counter.into_map(), expect);
+    /// ```
+    pub fn subtract<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            let mut remove = false;
+            if let Some(entry) = self.map.get_mut(&item) {
+                if *entry > N::zero() {
+                    *entry -= N::one();
+                }
+                remove = *entry == N::zero();
+            }
+            if remove {
+                self.map.remove(&item);
+            }
+        }
+    }
+}
+
+impl<T, N> BTreeCounter<T, N>
+where
+    T: Ord + Clone,
+    N: Clone + Ord,
+{
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common.
+    ///
+    /// Unlike [`Counter::most_common`], the ordering of duplicates is stable: items with the same
+    /// count are sorted in increasing order of their keys.
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let mc = "pappaopolo".chars().collect::<BTreeCounter<_>>().most_common();
+    /// let expected = vec![('p', 4), ('o', 3), ('a', 2), ('l', 1)];
+    /// assert_eq!(mc, expected);
+    /// ```
+    pub fn most_common(&self) -> Vec<(T, N)> {
+        let mut items = self
+            .map
+            .iter()
+            .map(|(key, count)| (key.clone(), count.clone()))
+            .collect::<Vec<_>>();
+        // The map iterates in key order, so a stable sort leaves ties in key order.
+        items.sort_by(|(_, a_count), (_, b_count)| b_count.cmp(a_count));
+        items
+    }
+
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common.
+    ///
+    /// In the event that two keys have an equal frequency, use the supplied ordering function
+    /// to further arrange the results.
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let counter = "eaddbbccc".chars().collect::<BTreeCounter<_>>();
+    /// let by_common = counter.most_common_tiebreaker(|&a, &b| b.cmp(&a));
+    /// let expected = vec![('c', 3), ('d', 2), ('b', 2), ('e', 1), ('a', 1)];
+    /// assert_eq!(by_common, expected);
+    /// ```
+    pub fn most_common_tiebreaker<F>(&self, mut tiebreaker: F) -> Vec<(T, N)>
+    where
+        F: FnMut(&T, &T) -> ::std::cmp::Ordering,
+    {
+        let mut items = self
+            .map
+            .iter()
+            .map(|(key, count)| (key.clone(), count.clone()))
+            .collect::<Vec<_>>();
+        items.sort_unstable_by(|(a_item, a_count), (b_item, b_count)| {
+            b_count
+                .cmp(a_count)
+                .then_with(|| tiebreaker(a_item, b_item))
+        });
+        items
+    }
+
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common.
+    ///
+    /// In the event that two keys have an equal frequency, use the natural ordering of the keys
+    /// to further sort the results. For a `BTreeCounter` this is the same as [`most_common`].
+    ///
+    /// [`most_common`]: BTreeCounter::most_common
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let mc = "abracadabra".chars().collect::<BTreeCounter<_>>().most_common_ordered();
+    /// let expect = vec![('a', 5), ('b', 2), ('r', 2), ('c', 1), ('d', 1)];
+    /// assert_eq!(mc, expect);
+    /// ```
+    pub fn most_common_ordered(&self) -> Vec<(T, N)> {
+        self.most_common()
+    }
+
+    /// Returns the `k` most common items in decreasing order of their counts.
+    ///
+    /// The returned vector is the same as would be obtained by calling `most_common_ordered` and
+    /// then truncating the result to length `k`. See [`Counter::k_most_common_ordered`] for a
+    /// discussion of the time complexity.
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let counter: BTreeCounter<_> = "abracadabra".chars().collect();
+    /// let top3 = counter.k_most_common_ordered(3);
+    /// assert_eq!(top3, vec![('a', 5), ('b', 2), ('r', 2)]);
+    /// ```
+    pub fn k_most_common_ordered(&self, k: usize) -> Vec<(T, N)> {
+        use std::cmp::Reverse;
+
+        if k == 0 {
+            return vec![];
+        }
+
+        if k >= self.map.len() {
+            return self.most_common_ordered();
+        }
+
+        let items = self.map.iter().map(|(t, n)| (Reverse(n.clone()), t));
+        let heap = k_smallest(items, k);
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|(Reverse(n), t)| (t.clone(), n))
+            .collect()
+    }
+}
+
+impl<T, N> BTreeCounter<T, N>
+where
+    T: Ord,
+    N: PartialOrd + Zero,
+{
+    /// Test whether this counter is a superset of another counter.
+    ///
+    /// See [`Counter::is_superset`].
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let c = "aaabbc".chars().collect::<BTreeCounter<_>>();
+    /// let mut d = "abb".chars().collect::<BTreeCounter<_>>();
+    ///
+    /// assert!(c.is_superset(&d));
+    /// d[&'e'] = 1;
+    /// assert!(!c.is_superset(&d));
+    /// ```
+    pub fn is_superset(&self, other: &Self) -> bool {
+        self.keys()
+            .chain(other.keys())
+            .all(|key| self[key] >= other[key])
+    }
+
+    /// Test whether this counter is a subset of another counter.
+    ///
+    /// See [`Counter::is_subset`].
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let mut c = "abb".chars().collect::<BTreeCounter<_>>();
+    /// let d = "aaabbc".chars().collect::<BTreeCounter<_>>();
+    ///
+    /// assert!(c.is_subset(&d));
+    /// c[&'e'] = 1;
+    /// assert!(!c.is_subset(&d));
+    /// ```
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.keys()
+            .chain(other.keys())
+            .all(|key| self[key] <= other[key])
+    }
+}
+
+impl<T, N> Default for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: Default,
+{
+    fn default() -> Self {
+        Self {
+            map: Default::default(),
+            zero: Default::default(),
+        }
+    }
+}
+
+impl<T, N, S> From<Counter<T, N, S>> for BTreeCounter<T, N>
+where
+    T: Hash + Ord,
+    N: Zero,
+{
+    /// Sort the items of a [`Counter`] into a `BTreeCounter`.
+    ///
+    /// ```rust
+    /// # use counter::{BTreeCounter, Counter};
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// let sorted = BTreeCounter::from(counter);
+    /// assert_eq!(sorted.keys().collect::<String>(), "abcdr");
+    /// ```
+    fn from(counter: Counter<T, N, S>) -> Self {
+        BTreeCounter {
+            map: counter.into_map().into_iter().collect(),
+            zero: N::zero(),
+        }
+    }
+}
+
+impl<T, N, S> From<BTreeCounter<T, N>> for Counter<T, N, S>
+where
+    T: Hash + Ord,
+    N: AddAssign + Zero,
+    S: BuildHasher + Default,
+{
+    /// Move the items of a `BTreeCounter` into a [`Counter`].
+    fn from(counter: BTreeCounter<T, N>) -> Self {
+        counter.map.into_iter().collect()
+    }
+}
+
+impl<T, N> AddAssign for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: Zero + AddAssign,
+{
+    /// Add another counter to this counter.
+    ///
+    /// `c += d;` -> `c[x] += d[x]` for all `x`
+    fn add_assign(&mut self, rhs: Self) {
+        for (key, value) in rhs.map {
+            let entry = self.map.entry(key).or_insert_with(N::zero);
+            *entry += value;
+        }
+    }
+}
+
+impl<T, N> Add for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: AddAssign + Zero,
+{
+    type Output = BTreeCounter<T, N>;
+
+    /// Add two counters together.
+    ///
+    /// `out = c + d;` -> `out[x] == c[x] + d[x]` for all `x`
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let c = "aaab".chars().collect::<BTreeCounter<_>>();
+    /// let d = "abb".chars().collect::<BTreeCounter<_>>();
+    ///
+    /// let e = c + d;
+    ///
+    /// assert_eq!(e.into_iter().collect::<Vec<_>>(), vec![('a', 4), ('b', 3)]);
+    /// ```
+    fn add(mut self, rhs: BTreeCounter<T, N>) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T, N> SubAssign for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: PartialOrd + PartialEq + SubAssign + Zero,
+{
+    /// Subtract (keeping only positive values).
+    ///
+    /// `c -= d;` -> `c[x] -= d[x]` for all `x`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    fn sub_assign(&mut self, rhs: Self) {
+        for (key, value) in rhs.map {
+            let mut remove = false;
+            if let Some(entry) = self.map.get_mut(&key) {
+                if *entry >= value {
+                    *entry -= value;
+                } else {
+                    remove = true;
+                }
+                if *entry == N::zero() {
+                    remove = true;
+                }
+            }
+            if remove {
+                self.map.remove(&key);
+            }
+        }
+    }
+}
+
+impl<T, N> Sub for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: PartialOrd + PartialEq + SubAssign + Zero,
+{
+    type Output = BTreeCounter<T, N>;
+
+    /// Subtract (keeping only positive values).
+    ///
+    /// `out = c - d;` -> `out[x] == c[x] - d[x]` for all `x`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let c = "aaab".chars().collect::<BTreeCounter<_>>();
+    /// let d = "abb".chars().collect::<BTreeCounter<_>>();
+    ///
+    /// let e = c - d;
+    ///
+    /// assert_eq!(e.into_iter().collect::<Vec<_>>(), vec![('a', 2)]);
+    /// ```
+    fn sub(mut self, rhs: BTreeCounter<T, N>) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<T, N> BitAnd for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: Ord + Zero,
+{
+    type Output = BTreeCounter<T, N>;
+
+    /// Returns the intersection of `self` and `rhs` as a new `BTreeCounter`.
+    ///
+    /// `out = c & d;` -> `out[x] == min(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let c = "aaab".chars().collect::<BTreeCounter<_>>();
+    /// let d = "abb".chars().collect::<BTreeCounter<_>>();
+    ///
+    /// let e = c & d;
+    ///
+    /// assert_eq!(e.into_iter().collect::<Vec<_>>(), vec![('a', 1), ('b', 1)]);
+    /// ```
+    fn bitand(mut self, rhs: BTreeCounter<T, N>) -> Self::Output {
+        self &= rhs;
+        self
+    }
+}
+
+impl<T, N> BitAndAssign for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: Ord + Zero,
+{
+    /// Updates `self` with the intersection of `self` and `rhs`
+    ///
+    /// `c &= d;` -> `c[x] == min(c[x], d[x])`
+    fn bitand_assign(&mut self, mut rhs: BTreeCounter<T, N>) {
+        self.map.retain(|key, lhs_count| match rhs.map.remove(key) {
+            Some(rhs_count) => {
+                if rhs_count < *lhs_count {
+                    *lhs_count = rhs_count;
+                }
+                true
+            }
+            None => false,
+        });
+    }
+}
+
+impl<T, N> BitOr for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: Ord + Zero,
+{
+    type Output = BTreeCounter<T, N>;
+
+    /// Returns the union of `self` and `rhs` as a new `BTreeCounter`.
+    ///
+    /// `out = c | d;` -> `out[x] == max(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::BTreeCounter;
+    /// let c = "aaab".chars().collect::<BTreeCounter<_>>();
+    /// let d = "abb".chars().collect::<BTreeCounter<_>>();
+    ///
+    /// let e = c | d;
+    ///
+    /// assert_eq!(e.into_iter().collect::<Vec<_>>(), vec![('a', 3), ('b', 2)]);
+    /// ```
+    fn bitor(mut self, rhs: BTreeCounter<T, N>) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl<T, N> BitOrAssign for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: Ord + Zero,
+{
+    /// Updates `self` with the union of `self` and `rhs`
+    ///
+    /// `c |= d;` -> `c[x] == max(c[x], d[x])`
+    fn bitor_assign(&mut self, rhs: BTreeCounter<T, N>) {
+        for (key, rhs_value) in rhs.map {
+            let entry = self.map.entry(key).or_insert_with(N::zero);
+            // See `impl BitOr for Counter` for why a weak inequality is used here.
+            if rhs_value >= *entry {
+                *entry = rhs_value;
+            }
+        }
+    }
+}
+
+impl<T, N> Deref for BTreeCounter<T, N>
+where
+    T: Ord,
+{
+    type Target = BTreeCounterMap<T, N>;
+    fn deref(&self) -> &BTreeCounterMap<T, N> {
+        &self.map
+    }
+}
+
+impl<T, N> DerefMut for BTreeCounter<T, N>
+where
+    T: Ord,
+{
+    fn deref_mut(&mut self) -> &mut BTreeCounterMap<T, N> {
+        &mut self.map
+    }
+}
+
+impl<'a, T, N> IntoIterator for &'a BTreeCounter<T, N>
+where
+    T: Ord,
+{
+    type Item = (&'a T, &'a N);
+    type IntoIter = std::collections::btree_map::Iter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.iter()
+    }
+}
+
+impl<T, N> IntoIterator for BTreeCounter<T, N>
+where
+    T: Ord,
+{
+    type Item = (T, N);
+    type IntoIter = std::collections::btree_map::IntoIter<T, N>;
+
+    /// Consumes the `BTreeCounter` to produce an iterator that owns the values it returns, in
+    /// increasing order of the keys.
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, T, N> IntoIterator for &'a mut BTreeCounter<T, N>
+where
+    T: Ord,
+{
+    type Item = (&'a T, &'a mut N);
+    type IntoIter = std::collections::btree_map::IterMut<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.iter_mut()
+    }
+}
+
+impl<T, Q, N> Index<&'_ Q> for BTreeCounter<T, N>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+    N: Zero,
+{
+    type Output = N;
+
+    /// Index in immutable contexts.
+    ///
+    /// Returns a reference to a [`zero`] value for missing keys.
+    ///
+    /// [`zero`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```
+    /// # use counter::BTreeCounter;
+    /// let counter = BTreeCounter::<_>::init("aabbcc".chars());
+    /// assert_eq!(counter[&'a'], 2);
+    /// assert_eq!(counter[&'d'], 0);
+    /// ```
+    fn index(&self, key: &'_ Q) -> &N {
+        self.map.get(key).unwrap_or(&self.zero)
+    }
+}
+
+impl<T, Q, N> IndexMut<&'_ Q> for BTreeCounter<T, N>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ToOwned<Owned = T> + ?Sized,
+    N: Zero,
+{
+    /// Index in mutable contexts.
+    ///
+    /// If the given key is not present, creates a new entry and initializes it with a [`zero`]
+    /// value.
+    ///
+    /// [`zero`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```
+    /// # use counter::BTreeCounter;
+    /// let mut counter = BTreeCounter::<_>::init("aabbcc".chars());
+    /// counter[&'c'] += 1;
+    /// counter[&'d'] += 1;
+    /// assert_eq!(counter[&'c'], 3);
+    /// assert_eq!(counter[&'d'], 1);
+    /// ```
+    fn index_mut(&mut self, key: &'_ Q) -> &mut N {
+        self.map.entry(key.to_owned()).or_insert_with(N::zero)
+    }
+}
+
+impl<I, T, N> AddAssign<I> for BTreeCounter<T, N>
+where
+    I: IntoIterator<Item = T>,
+    T: Ord,
+    N: AddAssign + Zero + One,
+{
+    /// Directly add the counts of the elements of `I` to `self`.
+    fn add_assign(&mut self, rhs: I) {
+        self.update(rhs);
+    }
+}
+
+impl<I, T, N> Add<I> for BTreeCounter<T, N>
+where
+    I: IntoIterator<Item = T>,
+    T: Ord,
+    N: AddAssign + Zero + One,
+{
+    type Output = Self;
+
+    /// Consume `self` producing a `BTreeCounter` like `self` updated with the counts of
+    /// the elements of `I`.
+    fn add(mut self, rhs: I) -> Self::Output {
+        self.update(rhs);
+        self
+    }
+}
+
+impl<I, T, N> SubAssign<I> for BTreeCounter<T, N>
+where
+    I: IntoIterator<Item = T>,
+    T: Ord,
+    N: PartialOrd + SubAssign + Zero + One,
+{
+    /// Directly subtract the counts of the elements of `I` from `self`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    fn sub_assign(&mut self, rhs: I) {
+        self.subtract(rhs);
+    }
+}
+
+impl<I, T, N> Sub<I> for BTreeCounter<T, N>
+where
+    I: IntoIterator<Item = T>,
+    T: Ord,
+    N: PartialOrd + SubAssign + Zero + One,
+{
+    type Output = Self;
+
+    /// Consume `self` producing a `BTreeCounter` like `self` with the counts of the
+    /// elements of `I` subtracted, keeping only positive values.
+    fn sub(mut self, rhs: I) -> Self::Output {
+        self.subtract(rhs);
+        self
+    }
+}
+
+impl<T, N> iter::FromIterator<T> for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: AddAssign + Zero + One,
+{
+    /// Produce a `BTreeCounter` from an iterator of items. This is called automatically
+    /// by [`Iterator::collect()`].
+    ///
+    /// [`Iterator::collect()`]:
+    /// https://doc.rust-lang.org/stable/std/iter/trait.Iterator.html#method.collect
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        BTreeCounter::<T, N>::init(iter)
+    }
+}
+
+impl<T, N> iter::FromIterator<(T, N)> for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: AddAssign + Zero,
+{
+    /// Creates a counter from `(item, count)` tuples.
+    ///
+    /// The counts of duplicate items are summed.
+    fn from_iter<I: IntoIterator<Item = (T, N)>>(iter: I) -> Self {
+        let mut cnt = BTreeCounter::new();
+        cnt.extend(iter);
+        cnt
+    }
+}
+
+impl<T, N> Extend<T> for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: AddAssign + Zero + One,
+{
+    /// Extend a `BTreeCounter` with an iterator of items.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.update(iter);
+    }
+}
+
+impl<T, N> Extend<(T, N)> for BTreeCounter<T, N>
+where
+    T: Ord,
+    N: AddAssign + Zero,
+{
+    /// Extend a counter with `(item, count)` tuples.
+    ///
+    /// The counts of duplicate items are summed.
+    fn extend<I: IntoIterator<Item = (T, N)>>(&mut self, iter: I) {
+        for (item, item_count) in iter {
+            let entry = self.map.entry(item).or_insert_with(N::zero);
+            *entry += item_count;
+        }
+    }
+}
+
+impl<'a, T: 'a, N: 'a> Extend<(&'a T, &'a N)> for BTreeCounter<T, N>
+where
+    T: Ord + Clone,
+    N: AddAssign + Zero + Clone,
+{
+    /// Extend a counter with `(item, count)` tuples.
+    ///
+    /// You can extend a `BTreeCounter` with another `BTreeCounter`, or with a [`Counter`]:
+    /// ```rust
+    /// # use counter::{BTreeCounter, Counter};
+    /// let mut counter = "abbccc".chars().collect::<BTreeCounter<_>>();
+    /// let another = "bccddd".chars().collect::<Counter<_>>();
+    /// counter.extend(&another);
+    /// let expect = vec![('a', 1), ('b', 3), ('c', 5), ('d', 3)];
+    /// assert_eq!(counter.into_iter().collect::<Vec<_>>(), expect);
+    /// ```
+    fn extend<I: IntoIterator<Item = (&'a T, &'a N)>>(&mut self, iter: I) {
+        for (item, item_count) in iter {
+            let entry = self.map.entry(item.clone()).or_insert_with(N::zero);
+            *entry += item_count.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::ops::Bound;
+
+    #[test]
+    fn test_update_iterates_in_order() {
+        let mut counter = BTreeCounter::init("cbbaaa".chars());
+        counter.update("dd".chars());
+        let items: Vec<_> = counter.into_iter().collect();
+        assert_eq!(items, vec![('a', 3), ('b', 2), ('c', 1), ('d', 2)]);
+    }
+
+    #[test]
+    fn test_subtract() {
+        let mut counter = BTreeCounter::<_>::init("abbccc".chars());
+        counter.subtract("bbccddd".chars());
+        let items: Vec<_> = counter.into_iter().collect();
+        assert_eq!(items, vec![('a', 1), ('c', 1)]);
+    }
+
+    #[test]
+    fn test_range_total() {
+        let counter: BTreeCounter<u32> = [1, 2, 2, 3, 3, 3, 5, 8, 8].iter().cloned().collect();
+        assert_eq!(counter.range_total::<_, _, usize>(..), 9);
+        assert_eq!(counter.range_total::<_, _, usize>(2..4), 5);
+        assert_eq!(counter.range_total::<_, _, usize>(4..=5), 1);
+        assert_eq!(counter.range_total::<_, _, usize>(9..), 0);
+    }
+
+    #[test]
+    fn test_range_total_borrowed_keys() {
+        let counter: BTreeCounter<String> = "apple banana cherry banana"
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        let range = (Bound::Included("b"), Bound::Excluded("c"));
+        assert_eq!(counter.range_total::<str, _, usize>(range), 2);
+    }
+
+    #[test]
+    fn test_operators() {
+        let c = || BTreeCounter::<_>::init("abbccc".chars());
+        let d = || BTreeCounter::<_>::init("bccddd".chars());
+
+        assert_eq!(c() + d(), BTreeCounter::init("abbbcccccddd".chars()));
+        assert_eq!(c() - d(), BTreeCounter::init("abc".chars()));
+        assert_eq!(c() & d(), BTreeCounter::init("bcc".chars()));
+        assert_eq!(c() | d(), BTreeCounter::init("abbcccddd".chars()));
+
+        let mut e = c();
+        e &= d();
+        assert_eq!(e, BTreeCounter::init("bcc".chars()));
+        let mut e = c();
+        e |= d();
+        assert_eq!(e, BTreeCounter::init("abbcccddd".chars()));
+    }
+
+    #[test]
+    fn test_index_zero() {
+        let counter = BTreeCounter::<_>::init("aaa".chars());
+        assert_eq!(counter[&'a'], 3);
+        assert_eq!(counter[&'b'], 0);
+        assert_eq!(counter.get(&'b'), None);
+    }
+
+    #[test]
+    fn test_k_most_common_ordered_matches_counter() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let values: Vec<u32> = (0..200).map(|_| rng.gen_range(0..=50)).collect();
+
+        let counter: BTreeCounter<_> = values.iter().cloned().collect();
+        let hashed: Counter<_> = values.into_iter().collect();
+        let all = counter.most_common_ordered();
+        assert_eq!(all, hashed.most_common_ordered());
+        for k in 0..=counter.len() {
+            assert_eq!(counter.k_most_common_ordered(k), &all[..k]);
+        }
+    }
+
+    #[test]
+    fn test_conversions() {
+        let counter = Counter::<_>::init("abracadabra".chars());
+        let sorted = BTreeCounter::from(counter.clone());
+        assert_eq!(sorted.total::<usize>(), 11);
+        let back: Counter<_> = sorted.into();
+        assert_eq!(back, counter);
+    }
+}
@@ -0,0 +1,144 @@
+//! Iterating over a counter from the most to the least common item.
+
+use crate::Counter;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::iter::FusedIterator;
+
+/// An iterator over the items of a counter and their counts, from most to least common.
+///
+/// Items with the same count are visited in increasing order of their keys, as in
+/// [`Counter::most_common_ordered`]. The items are sorted lazily: creating the iterator takes
+/// time linear in the number of items, and each step takes logarithmic time.
+///
+/// This `struct` is created by [`Counter::iter_by_count`]. See its documentation for more.
+#[derive(Debug)]
+pub struct IterByCount<'a, T, N> {
+    heap: BinaryHeap<(&'a N, Reverse<&'a T>)>,
+}
+
+// Written by hand since the derive would require `T: Clone`, but only the references are cloned.
+impl<'a, T, N: Ord> Clone for IterByCount<'a, T, N> {
+    fn clone(&self) -> Self {
+        IterByCount {
+            heap: self.heap.clone(),
+        }
+    }
+}
+
+impl<'a, T, N> Iterator for IterByCount<'a, T, N>
+where
+    T: Ord,
+    N: Ord,
+{
+    type Item = (&'a T, &'a N);
+
+    fn next(&mut self) -> Option<(&'a T, &'a N)> {
+        let (count, Reverse(item)) = self.heap.pop()?;
+        Some((item, count))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<'a, T, N> ExactSizeIterator for IterByCount<'a, T, N>
+where
+    T: Ord,
+    N: Ord,
+{
+}
+
+impl<'a, T, N> FusedIterator for IterByCount<'a, T, N>
+where
+    T: Ord,
+    N: Ord,
+{
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Ord,
+    N: Ord,
+{
+    /// Returns an iterator over the items and their counts, from most to least common.
+    ///
+    /// This yields the same items in the same order as [`most_common_ordered()`], but without
+    /// cloning them, and without sorting the items which are never reached. That makes it the
+    /// better choice when the number of items wanted isn't known up front, such as when
+    /// stopping at a threshold count.
+    ///
+    /// [`most_common_ordered()`]: Counter::most_common_ordered
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// let frequent = counter
+    ///     .iter_by_count()
+    ///     .take_while(|&(_, &count)| count >= 2)
+    ///     .map(|(&item, _)| item)
+    ///     .collect::<String>();
+    /// assert_eq!(frequent, "abr");
+    ///
+    /// let second_page = counter.iter_by_count().skip(2).take(2).collect::<Vec<_>>();
+    /// assert_eq!(second_page, vec![(&'r', &2), (&'c', &1)]);
+    /// ```
+    pub fn iter_by_count(&self) -> IterByCount<'_, T, N> {
+        IterByCount {
+            heap: self
+                .map
+                .iter()
+                .map(|(item, count)| (count, Reverse(item)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Counter;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_iter_by_count_matches_most_common_ordered() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let counter: Counter<_> = (0..500).map(|_| rng.gen_range(0..60)).collect();
+        let by_count = counter
+            .iter_by_count()
+            .map(|(&item, &count)| (item, count))
+            .collect::<Vec<_>>();
+        assert_eq!(by_count, counter.most_common_ordered());
+    }
+
+    #[test]
+    fn test_iter_by_count_len() {
+        let counter = Counter::<_>::init("abracadabra".chars());
+        let mut iter = counter.iter_by_count();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some((&'a', &5)));
+        assert_eq!(iter.len(), 4);
+        assert!(iter.clone().eq(iter.clone()));
+        assert_eq!(iter.by_ref().count(), 4);
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(Counter::<char>::new().iter_by_count().next(), None);
+    }
+
+    #[test]
+    fn test_iter_by_count_clone_without_clone_keys() {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct Key(u8);
+
+        let counter = vec![Key(1), Key(2), Key(2)]
+            .into_iter()
+            .collect::<Counter<_>>();
+        let mut iter = counter.iter_by_count();
+        let copy = iter.clone();
+        assert_eq!(iter.next(), Some((&Key(2), &2)));
+        assert_eq!(copy.collect::<Vec<_>>(), [(&Key(2), &2), (&Key(1), &1)]);
+    }
+}
@@ -0,0 +1,422 @@
+//! A counter which many threads can update at once.
+
+use crate::{hash_one, Counter};
+
+use num_traits::{One, Zero};
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::ops::AddAssign;
+use std::sync::{Mutex, MutexGuard};
+
+/// A thread-safe counter, which can be updated through a shared reference.
+///
+/// Items are spread over a number of independently locked shards according to their hash, so
+/// threads which count different items rarely wait on each other. Once counting is done, take a
+/// [`snapshot()`] or call [`into_counter()`] to get a plain [`Counter`] back; in the meantime, the
+/// [`most_common()`] family and [`total()`] are available directly.
+///
+/// [`snapshot()`]: ConcurrentCounter::snapshot
+/// [`into_counter()`]: ConcurrentCounter::into_counter
+/// [`most_common()`]: ConcurrentCounter::most_common
+/// [`total()`]: ConcurrentCounter::total
+///
+/// ```rust
+/// # use counter::ConcurrentCounter;
+/// let counter = ConcurrentCounter::<_>::new();
+/// std::thread::scope(|scope| {
+///     for word in "able babble table babble".split_whitespace() {
+///         let counter = &counter;
+///         scope.spawn(move || counter.increment(word));
+///     }
+/// });
+/// assert_eq!(counter.get("babble"), 2);
+/// assert_eq!(counter.total(), 4);
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentCounter<T, N = usize, S = RandomState> {
+    shards: Box<[Mutex<HashMap<T, N, S>>]>,
+    hash_builder: S,
+}
+
+/// The number of shards used by [`ConcurrentCounter::new`] per available thread.
+const SHARDS_PER_THREAD: usize = 4;
+
+impl<T, N> ConcurrentCounter<T, N>
+where
+    T: Hash + Eq,
+{
+    /// Create a new, empty `ConcurrentCounter`, with a number of shards suited to the available
+    /// parallelism of the machine.
+    pub fn new() -> ConcurrentCounter<T, N> {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_shards(threads * SHARDS_PER_THREAD)
+    }
+
+    /// Create a new, empty `ConcurrentCounter` with the given number of shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    pub fn with_shards(shards: usize) -> ConcurrentCounter<T, N> {
+        Self::with_shards_and_hasher(shards, RandomState::new())
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher + Clone,
+{
+    /// Create a new, empty `ConcurrentCounter` with the given number of shards, which will use
+    /// the given hash builder to hash its items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    pub fn with_shards_and_hasher(shards: usize, hash_builder: S) -> ConcurrentCounter<T, N, S> {
+        assert!(shards > 0, "a ConcurrentCounter needs at least one shard");
+        ConcurrentCounter {
+            shards: (0..shards)
+                .map(|_| Mutex::new(HashMap::with_hasher(hash_builder.clone())))
+                .collect(),
+            hash_builder,
+        }
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    /// Lock the shard which holds `key`.
+    fn shard<Q>(&self, key: &Q) -> MutexGuard<'_, HashMap<T, N, S>>
+    where
+        Q: Hash + ?Sized,
+    {
+        // `HashMap` picks buckets with the low bits of the hash, and all the keys of a shard
+        // would otherwise share those. Select the shard with higher bits instead.
+        let index = (hash_one(&self.hash_builder, key) >> 32) as usize % self.shards.len();
+        lock(&self.shards[index])
+    }
+
+    /// Returns the number of shards of this counter.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the number of distinct items in the counter.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).len()).sum()
+    }
+
+    /// Returns `true` if the counter holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| lock(shard).is_empty())
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Zero,
+    S: BuildHasher,
+{
+    /// Consumes this counter and returns a [`Counter`] holding the same counts.
+    pub fn into_counter(self) -> Counter<T, N, S> {
+        let mut counter = Counter::with_hasher(self.hash_builder);
+        for shard in self.shards.into_vec() {
+            let shard = shard.into_inner().unwrap_or_else(|e| e.into_inner());
+            counter.map.extend(shard);
+        }
+        counter
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero + One,
+    S: BuildHasher,
+{
+    /// Add one to the count of `key`.
+    ///
+    /// The key is only cloned when it is counted for the first time.
+    pub fn increment<Q>(&self, key: &Q)
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = T> + ?Sized,
+    {
+        self.increment_by(key, N::one());
+    }
+
+    /// Add the counts of the elements from the given iterable to this counter.
+    pub fn update<I>(&self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            let mut shard = self.shard(&item);
+            let entry = shard.entry(item).or_insert_with(N::zero);
+            *entry += N::one();
+        }
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero,
+    S: BuildHasher,
+{
+    /// Add `count` to the count of `key`.
+    ///
+    /// As in a [`Counter`], no item is kept with a count of zero: adding zero does nothing, and
+    /// `key` is removed if its count becomes zero.
+    pub fn increment_by<Q>(&self, key: &Q, count: N)
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = T> + ?Sized,
+    {
+        if count.is_zero() {
+            return;
+        }
+        let mut shard = self.shard(key);
+        let cancelled = match shard.get_mut(key) {
+            Some(entry) => {
+                *entry += count;
+                entry.is_zero()
+            }
+            None => {
+                shard.insert(key.to_owned(), count);
+                false
+            }
+        };
+        if cancelled {
+            shard.remove(key);
+        }
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Clone + Zero,
+    S: BuildHasher,
+{
+    /// Returns the count of `key`, or zero if it has not been counted.
+    pub fn get<Q>(&self, key: &Q) -> N
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shard(key).get(key).cloned().unwrap_or_else(N::zero)
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Clone + Zero + AddAssign,
+    S: BuildHasher,
+{
+    /// Returns the sum of the counts.
+    ///
+    /// Shards are locked one at a time, so counts which are added concurrently may or may not be
+    /// included.
+    pub fn total(&self) -> N {
+        let mut total = N::zero();
+        for shard in self.shards.iter() {
+            for count in lock(shard).values() {
+                total += count.clone();
+            }
+        }
+        total
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Clone + Zero,
+    S: BuildHasher + Clone,
+{
+    /// Returns a [`Counter`] holding a copy of the current counts.
+    ///
+    /// Shards are locked one at a time, so the snapshot is not atomic with respect to concurrent
+    /// updates of different shards.
+    pub fn snapshot(&self) -> Counter<T, N, S> {
+        let mut counter = Counter::with_hasher(self.hash_builder.clone());
+        for shard in self.shards.iter() {
+            let shard = lock(shard);
+            counter.map.extend(
+                shard
+                    .iter()
+                    .map(|(key, count)| (key.clone(), count.clone())),
+            );
+        }
+        counter
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Clone + Ord + Zero,
+    S: BuildHasher + Clone,
+{
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common.
+    ///
+    /// See [`Counter::most_common`].
+    pub fn most_common(&self) -> Vec<(T, N)> {
+        self.snapshot().most_common()
+    }
+
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common, using the
+    /// supplied ordering function to break ties.
+    ///
+    /// See [`Counter::most_common_tiebreaker`].
+    pub fn most_common_tiebreaker<F>(&self, tiebreaker: F) -> Vec<(T, N)>
+    where
+        F: FnMut(&T, &T) -> ::std::cmp::Ordering,
+    {
+        self.snapshot().most_common_tiebreaker(tiebreaker)
+    }
+}
+
+impl<T, N, S> ConcurrentCounter<T, N, S>
+where
+    T: Hash + Eq + Clone + Ord,
+    N: Clone + Ord + Zero,
+    S: BuildHasher + Clone,
+{
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common, using the
+    /// natural ordering of the keys to break ties.
+    ///
+    /// See [`Counter::most_common_ordered`].
+    pub fn most_common_ordered(&self) -> Vec<(T, N)> {
+        self.snapshot().most_common_ordered()
+    }
+
+    /// Returns the `k` most common items in decreasing order of their counts.
+    ///
+    /// See [`Counter::k_most_common_ordered`].
+    pub fn k_most_common_ordered(&self, k: usize) -> Vec<(T, N)> {
+        self.snapshot().k_most_common_ordered(k)
+    }
+}
+
+impl<T, N> Default for ConcurrentCounter<T, N>
+where
+    T: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, N, S> From<ConcurrentCounter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Zero,
+    S: BuildHasher,
+{
+    fn from(counter: ConcurrentCounter<T, N, S>) -> Self {
+        counter.into_counter()
+    }
+}
+
+/// Lock a shard, recovering its contents if another thread panicked while holding the lock.
+///
+/// Every update of a shard leaves it in a consistent state, so a poisoned shard is still usable.
+fn lock<M>(shard: &Mutex<M>) -> MutexGuard<'_, M> {
+    shard.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_increment_across_threads() {
+        let counter = ConcurrentCounter::<u32>::with_shards(8);
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for i in 0..1000 {
+                        counter.increment(&(i % 10));
+                    }
+                });
+            }
+        });
+        assert_eq!(counter.len(), 10);
+        assert_eq!(counter.total(), 8000);
+        for i in 0..10 {
+            assert_eq!(counter.get(&i), 800);
+        }
+    }
+
+    #[test]
+    fn test_update_matches_counter() {
+        // The threads share keys, and 'b' and 'c' tie for the highest count.
+        let batches = ["aaaabbbcc", "abbbbccd", "cccdde", "a"];
+        let counter = ConcurrentCounter::<_>::new();
+        std::thread::scope(|scope| {
+            for batch in &batches {
+                let counter = &counter;
+                scope.spawn(move || counter.update(batch.chars()));
+            }
+        });
+        let expected = Counter::init(batches.concat().chars());
+        assert_eq!(counter.snapshot(), expected);
+        assert_eq!(
+            counter.k_most_common_ordered(3),
+            expected.k_most_common_ordered(3)
+        );
+        assert_eq!(counter.into_counter(), expected);
+    }
+
+    #[test]
+    fn test_increment_borrowed_key() {
+        let counter = ConcurrentCounter::<String>::with_shards(2);
+        counter.increment("a");
+        counter.increment_by("a", 2);
+        counter.increment("b");
+        assert_eq!(counter.get("a"), 3);
+        assert_eq!(counter.get("c"), 0);
+        assert_eq!(
+            counter.most_common_ordered(),
+            vec![("a".to_string(), 3), ("b".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_increment_by_keeps_no_zero_counts() {
+        let counter = ConcurrentCounter::<char, i32>::with_shards(2);
+        counter.increment_by(&'a', 0);
+        assert!(counter.is_empty());
+
+        counter.increment_by(&'a', 2);
+        counter.increment_by(&'b', 1);
+        counter.increment_by(&'a', -2);
+        assert_eq!(counter.len(), 1);
+        assert_eq!(counter.snapshot(), Counter::init("b".chars()));
+    }
+
+    #[test]
+    fn test_empty() {
+        let counter = ConcurrentCounter::<char>::with_shards(1);
+        assert!(counter.is_empty());
+        assert_eq!(counter.total(), 0);
+        assert!(counter.most_common().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_shards() {
+        let _ = ConcurrentCounter::<char>::with_shards(0);
+    }
+}
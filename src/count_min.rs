@@ -0,0 +1,352 @@
+//! An approximate counter for key spaces too large to count exactly.
+
+use crate::{hash_one, Counter};
+
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hash};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign};
+
+/// A [Count-Min Sketch], which estimates the counts of items in a fixed amount of memory.
+///
+/// The sketch is a grid of `depth` rows of `width` counters. Each item is hashed to one counter
+/// per row, and its count is estimated by the smallest of those counters. Estimates are never
+/// lower than the true count. With a width of ⌈*e* / ε⌉ and a depth of ⌈ln(1 / δ)⌉, an
+/// estimate exceeds the true count by more than ε times the [`total()`] with probability at most
+/// δ; [`with_error()`] picks the dimensions this way.
+///
+/// Sketches with the same dimensions hash items identically, so they can be merged with `+`.
+///
+/// [Count-Min Sketch]: https://en.wikipedia.org/wiki/Count%E2%80%93min_sketch
+/// [`total()`]: CountMinSketch::total
+/// [`with_error()`]: CountMinSketch::with_error
+///
+/// ```rust
+/// # use counter::CountMinSketch;
+/// let mut sketch = CountMinSketch::new(1000, 5);
+/// sketch.update("able babble table babble rabble table able".split_whitespace());
+/// assert!(sketch.estimate("babble") >= 2);
+/// assert!(sketch.estimate("table") >= 2);
+/// assert_eq!(sketch.total(), 7);
+/// ```
+pub struct CountMinSketch<T: ?Sized> {
+    width: usize,
+    depth: usize,
+    // `depth` rows of `width` counters each, stored row after row.
+    counts: Vec<usize>,
+    total: usize,
+    item: PhantomData<fn(&T)>,
+}
+
+/// Every sketch hashes with the same fixed keys, which is what makes sketches mergeable.
+pub(crate) type SketchState = BuildHasherDefault<DefaultHasher>;
+
+impl<T: ?Sized> CountMinSketch<T> {
+    /// Create a new, empty sketch with `depth` rows of `width` counters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `depth` is zero.
+    pub fn new(width: usize, depth: usize) -> CountMinSketch<T> {
+        assert!(width > 0, "a CountMinSketch needs a width of at least one");
+        assert!(depth > 0, "a CountMinSketch needs a depth of at least one");
+        CountMinSketch {
+            width,
+            depth,
+            counts: vec![0; width * depth],
+            total: 0,
+            item: PhantomData,
+        }
+    }
+
+    /// Create a new, empty sketch whose estimates exceed the true counts by at most `epsilon`
+    /// times the total count, except with probability `delta`.
+    ///
+    /// ```rust
+    /// # use counter::CountMinSketch;
+    /// let sketch = CountMinSketch::<str>::with_error(0.001, 0.01);
+    /// assert_eq!(sketch.width(), 2719);
+    /// assert_eq!(sketch.depth(), 5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `epsilon` is positive and `delta` lies strictly between zero and one.
+    pub fn with_error(epsilon: f64, delta: f64) -> CountMinSketch<T> {
+        assert!(epsilon > 0.0, "epsilon must be positive");
+        assert!(delta > 0.0 && delta < 1.0, "delta must lie between 0 and 1");
+        let width = (std::f64::consts::E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil().max(1.0) as usize;
+        CountMinSketch::new(width, depth)
+    }
+
+    /// Returns the number of counters in each row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the sum of all the counts added to the sketch.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the position of the counter for `key` in each row.
+    fn columns<Q>(&self, key: &Q) -> impl Iterator<Item = usize>
+    where
+        Q: Hash + ?Sized,
+    {
+        // Derive the row hashes from two halves of a single hash, as described by Kirsch and
+        // Mitzenmacher in "Less Hashing, Same Performance: Building a Better Bloom Filter".
+        let hash = hash_one(&SketchState::default(), key);
+        let (h1, h2) = (hash & 0xffff_ffff, (hash >> 32) | 1);
+        let width = self.width as u64;
+        (0..self.depth as u64)
+            .map(move |row| (row * width + h1.wrapping_add(row.wrapping_mul(h2)) % width) as usize)
+    }
+}
+
+impl<T> CountMinSketch<T>
+where
+    T: Hash + ?Sized,
+{
+    /// Add one to the count of `key`.
+    pub fn increment<Q>(&mut self, key: &Q)
+    where
+        T: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        self.increment_by(key, 1);
+    }
+
+    /// Add `count` to the count of `key`.
+    pub fn increment_by<Q>(&mut self, key: &Q, count: usize)
+    where
+        T: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        for column in self.columns(key) {
+            self.counts[column] += count;
+        }
+        self.total += count;
+    }
+
+    /// Returns the estimated count of `key`.
+    ///
+    /// The estimate is never lower than the true count.
+    pub fn estimate<Q>(&self, key: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        self.columns(key)
+            .map(|column| self.counts[column])
+            .min()
+            .expect("a CountMinSketch has at least one row")
+    }
+}
+
+impl<T> CountMinSketch<T>
+where
+    T: Hash,
+{
+    /// Add the counts of the elements from the given iterable to this sketch.
+    pub fn update<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            self.increment(&item);
+        }
+    }
+}
+
+impl<T> CountMinSketch<T>
+where
+    T: Hash + Eq,
+{
+    /// Create a [`Counter`] of the estimated counts of the given candidate keys.
+    ///
+    /// Keys whose estimated count is zero are left out.
+    ///
+    /// ```rust
+    /// # use counter::CountMinSketch;
+    /// let mut sketch = CountMinSketch::new(1000, 5);
+    /// sketch.update("abracadabra".chars());
+    /// let counter = sketch.to_counter("abcdefg".chars());
+    /// assert!(counter[&'a'] >= 5);
+    /// assert!(counter.len() >= 4);
+    /// ```
+    pub fn to_counter<I>(&self, keys: I) -> Counter<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut counter = Counter::new();
+        for key in keys {
+            let estimate = self.estimate(&key);
+            if estimate > 0 {
+                counter.map.insert(key, estimate);
+            }
+        }
+        counter
+    }
+}
+
+impl<T: ?Sized> Clone for CountMinSketch<T> {
+    fn clone(&self) -> Self {
+        CountMinSketch {
+            width: self.width,
+            depth: self.depth,
+            counts: self.counts.clone(),
+            total: self.total,
+            item: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for CountMinSketch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountMinSketch")
+            .field("width", &self.width)
+            .field("depth", &self.depth)
+            .field("total", &self.total)
+            .finish()
+    }
+}
+
+impl<T: ?Sized> AddAssign for CountMinSketch<T> {
+    /// Merge another sketch into this one.
+    ///
+    /// The result is the same as if all the items added to `rhs` had been added to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two sketches have different dimensions.
+    fn add_assign(&mut self, rhs: Self) {
+        assert!(
+            self.width == rhs.width && self.depth == rhs.depth,
+            "cannot merge sketches with different dimensions"
+        );
+        for (count, rhs_count) in self.counts.iter_mut().zip(rhs.counts) {
+            *count += rhs_count;
+        }
+        self.total += rhs.total;
+    }
+}
+
+impl<T: ?Sized> Add for CountMinSketch<T> {
+    type Output = CountMinSketch<T>;
+
+    /// Merge two sketches.
+    ///
+    /// ```rust
+    /// # use counter::CountMinSketch;
+    /// let mut a = CountMinSketch::new(100, 4);
+    /// a.update("aaab".chars());
+    /// let mut b = CountMinSketch::new(100, 4);
+    /// b.update("abb".chars());
+    ///
+    /// let c = a + b;
+    /// assert!(c.estimate(&'a') >= 4);
+    /// assert_eq!(c.total(), 7);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two sketches have different dimensions.
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T> Extend<T> for CountMinSketch<T>
+where
+    T: Hash,
+{
+    /// Extend a sketch with an iterator of items.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.update(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_never_underestimates() {
+        let values: Vec<u32> = (0..10_000).map(|i| i * i % 2000).collect();
+
+        let mut sketch = CountMinSketch::new(200, 4);
+        sketch.update(values.iter().cloned());
+        let counter: Counter<_> = values.into_iter().collect();
+        for (key, &count) in counter.iter() {
+            assert!(sketch.estimate(key) >= count);
+        }
+        assert_eq!(sketch.total(), 10_000);
+    }
+
+    #[test]
+    fn test_error_bound() {
+        let values: Vec<u32> = (0..10_000).map(|i| i * i % 5000).collect();
+
+        let epsilon = 0.01;
+        let mut sketch = CountMinSketch::with_error(epsilon, 0.001);
+        sketch.update(values.iter().cloned());
+        let counter: Counter<_> = values.into_iter().collect();
+        let bound = (epsilon * sketch.total() as f64) as usize;
+        let over = counter
+            .iter()
+            .filter(|(key, &count)| sketch.estimate(*key) > count + bound)
+            .count();
+        // With delta = 0.001 we expect about 0.1% of the keys to exceed the bound.
+        assert!(over <= counter.len() / 100, "{} of {}", over, counter.len());
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        let mut sketch = CountMinSketch::<String>::new(64, 3);
+        sketch.increment("able");
+        sketch.increment_by("table", 3);
+        assert!(sketch.estimate("able") >= 1);
+        assert!(sketch.estimate("table") >= 3);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = CountMinSketch::new(50, 3);
+        a.update("able babble table".split_whitespace());
+        let mut b = CountMinSketch::new(50, 3);
+        b.update("babble rabble".split_whitespace());
+        let mut combined = CountMinSketch::new(50, 3);
+        combined.update("able babble table babble rabble".split_whitespace());
+
+        let merged = a + b;
+        assert_eq!(merged.counts, combined.counts);
+        assert_eq!(merged.total(), combined.total());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_mismatched_dimensions() {
+        let _ = CountMinSketch::<char>::new(50, 3) + CountMinSketch::new(50, 4);
+    }
+
+    #[test]
+    fn test_to_counter() {
+        let mut sketch = CountMinSketch::new(1000, 5);
+        sketch.update("abbccc".chars());
+        let counter = sketch.to_counter("abcd".chars());
+        assert!(counter[&'a'] >= 1);
+        assert!(counter[&'b'] >= 2);
+        assert!(counter[&'c'] >= 3);
+        assert!(counter.len() >= 3);
+    }
+}
@@ -0,0 +1,374 @@
+//! Probability distributions estimated from counts.
+
+use crate::Counter;
+
+use num_traits::ToPrimitive;
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How a [`Distribution`] shifts probability from the counted keys to the keys which were never
+/// counted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Smoothing {
+    /// Maximum likelihood: every key gets its share of the total count, and keys which were
+    /// never counted get nothing.
+    None,
+    /// Add one to the count of every key of the vocabulary.
+    Laplace,
+    /// Add the given amount to the count of every key of the vocabulary.
+    Lidstone(f64),
+    /// Simple [Good-Turing] estimation: keys which were never counted share the probability
+    /// of the keys which were counted once, and a key counted `c` times is treated as if it had
+    /// been counted `(c + 1) * n(c + 1) / n(c)` times, where `n(c)` is the number of keys counted
+    /// `c` times. Counts for which `n(c + 1)` is zero are left as they are.
+    ///
+    /// Counts are rounded to whole numbers, and keys whose count rounds to zero are treated as
+    /// never counted.
+    ///
+    /// [Good-Turing]: https://en.wikipedia.org/wiki/Good%E2%80%93Turing_frequency_estimation
+    GoodTuring,
+}
+
+/// A probability distribution over keys, estimated from the counts of a [`Counter`].
+///
+/// The distribution covers a vocabulary of a given size, which includes all the counted keys.
+/// Each key of the vocabulary which was never counted has the same [`unseen_probability()`],
+/// which [`prob()`] also returns for any key it does not know. Only the keys with a positive
+/// count are considered counted.
+///
+/// [`unseen_probability()`]: Distribution::unseen_probability
+/// [`prob()`]: Distribution::prob
+///
+/// ```rust
+/// # use counter::{Counter, Distribution, Smoothing};
+/// let counter = "the cat sat on the mat".split_whitespace().collect::<Counter<_>>();
+///
+/// let mle = Distribution::new(&counter);
+/// assert_eq!(mle.prob("the"), 2.0 / 6.0);
+/// assert_eq!(mle.prob("dog"), 0.0);
+///
+/// // A vocabulary of 10 words, of which 5 were seen.
+/// let laplace = Distribution::smoothed(&counter, Smoothing::Laplace, 10);
+/// assert_eq!(laplace.prob("the"), 3.0 / 16.0);
+/// assert_eq!(laplace.prob("dog"), 1.0 / 16.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Distribution<T> {
+    probs: HashMap<T, f64>,
+    unseen: f64,
+    vocab_size: usize,
+}
+
+impl<T> Distribution<T>
+where
+    T: Hash + Eq + Clone,
+{
+    /// Create the maximum likelihood distribution of the counts, whose vocabulary is just the
+    /// counted keys.
+    pub fn new<N, S>(counter: &Counter<T, N, S>) -> Distribution<T>
+    where
+        N: ToPrimitive,
+    {
+        Self::smoothed(counter, Smoothing::None, 0)
+    }
+
+    /// Create a distribution of the counts over a vocabulary of `vocab_size` keys, using the
+    /// given smoothing.
+    ///
+    /// A `vocab_size` smaller than the number of counted keys is raised to that number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [`Smoothing::Lidstone`] amount is negative.
+    pub fn smoothed<N, S>(
+        counter: &Counter<T, N, S>,
+        smoothing: Smoothing,
+        vocab_size: usize,
+    ) -> Distribution<T>
+    where
+        N: ToPrimitive,
+    {
+        let (counts, total) = counter.positive_counts();
+        let counts = counts.collect::<Vec<_>>();
+        let vocab_size = vocab_size.max(counts.len());
+
+        let (probs, unseen) = match smoothing {
+            Smoothing::None => (Self::normalize(&counts, 0.0, total), 0.0),
+            Smoothing::Laplace => Self::add_k(&counts, 1.0, total, vocab_size),
+            Smoothing::Lidstone(k) => {
+                assert!(k >= 0.0, "the Lidstone amount must not be negative");
+                Self::add_k(&counts, k, total, vocab_size)
+            }
+            Smoothing::GoodTuring => {
+                let counts = counts
+                    .iter()
+                    .map(|&(item, count)| (item, count.round() as u64))
+                    .filter(|&(_, count)| count > 0)
+                    .collect::<Vec<_>>();
+                let total = counts.iter().map(|&(_, count)| count).sum::<u64>() as f64;
+                let unseen_keys = (vocab_size - counts.len()) as f64;
+                let counts_of_counts = counts
+                    .iter()
+                    .copied()
+                    .collect::<Counter<&T, u64>>()
+                    .counts_of_counts();
+                let of = |c: u64| counts_of_counts[&c] as f64;
+
+                // The counted keys share whatever the unseen keys leave over.
+                let unseen_mass = if unseen_keys > 0.0 && total > 0.0 {
+                    of(1) / total
+                } else {
+                    0.0
+                };
+                let adjusted = counts
+                    .iter()
+                    .map(|&(item, c)| {
+                        let next = of(c + 1);
+                        let count = if next > 0.0 {
+                            (c + 1) as f64 * next / of(c)
+                        } else {
+                            c as f64
+                        };
+                        (item, count)
+                    })
+                    .collect::<Vec<_>>();
+                let adjusted_total = adjusted.iter().map(|&(_, count)| count).sum::<f64>();
+                let probs = Self::normalize(&adjusted, 0.0, adjusted_total / (1.0 - unseen_mass));
+                let unseen = if unseen_keys > 0.0 {
+                    unseen_mass / unseen_keys
+                } else {
+                    0.0
+                };
+                (probs, unseen)
+            }
+        };
+        Distribution {
+            probs,
+            unseen,
+            vocab_size,
+        }
+    }
+
+    fn add_k(
+        counts: &[(&T, f64)],
+        k: f64,
+        total: f64,
+        vocab_size: usize,
+    ) -> (HashMap<T, f64>, f64) {
+        let denominator = total + k * vocab_size as f64;
+        let unseen = if denominator > 0.0 {
+            k / denominator
+        } else {
+            0.0
+        };
+        (Self::normalize(counts, k, denominator), unseen)
+    }
+
+    fn normalize(counts: &[(&T, f64)], k: f64, denominator: f64) -> HashMap<T, f64> {
+        counts
+            .iter()
+            .map(|&(item, count)| (item.clone(), (count + k) / denominator))
+            .collect()
+    }
+}
+
+impl<T> Distribution<T>
+where
+    T: Hash + Eq,
+{
+    /// Returns the probability of `key`.
+    ///
+    /// Keys which were never counted have the [`unseen_probability()`].
+    ///
+    /// [`unseen_probability()`]: Distribution::unseen_probability
+    pub fn prob<Q>(&self, key: &Q) -> f64
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.probs.get(key).copied().unwrap_or(self.unseen)
+    }
+
+    /// Returns the probability of each key of the vocabulary which was never counted.
+    pub fn unseen_probability(&self) -> f64 {
+        self.unseen
+    }
+
+    /// Give each key of the vocabulary which was never counted the probability `unseen`, and
+    /// scale the probabilities of the counted keys to make up the rest.
+    ///
+    /// If every key of the vocabulary was counted, this only changes what [`prob()`] returns
+    /// for keys outside the vocabulary.
+    ///
+    /// [`prob()`]: Distribution::prob
+    ///
+    /// ```rust
+    /// # use counter::{Counter, Distribution, Smoothing};
+    /// let counter = "aaab".chars().collect::<Counter<_>>();
+    /// let dist = Distribution::smoothed(&counter, Smoothing::None, 4).with_unseen_probability(0.1);
+    /// assert_eq!(dist.prob(&'z'), 0.1);
+    /// assert!((dist.prob(&'a') - 0.6).abs() < 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `unseen` is negative, or if the unseen keys would have more than all of the
+    /// probability between them.
+    pub fn with_unseen_probability(mut self, unseen: f64) -> Distribution<T> {
+        let unseen_keys = (self.vocab_size - self.probs.len()) as f64;
+        let seen_mass = 1.0 - unseen * unseen_keys;
+        assert!(unseen >= 0.0, "probabilities must not be negative");
+        assert!(
+            seen_mass >= 0.0,
+            "the unseen keys cannot have more than all the probability"
+        );
+        let current = self.probs.values().sum::<f64>();
+        if current > 0.0 {
+            for prob in self.probs.values_mut() {
+                *prob *= seen_mass / current;
+            }
+        }
+        self.unseen = unseen;
+        self
+    }
+
+    /// Returns the number of keys in the vocabulary, counted or not.
+    pub fn vocab_size(&self) -> usize {
+        self.vocab_size
+    }
+
+    /// Returns the number of counted keys.
+    pub fn len(&self) -> usize {
+        self.probs.len()
+    }
+
+    /// Returns `true` if no keys were counted.
+    pub fn is_empty(&self) -> bool {
+        self.probs.is_empty()
+    }
+
+    /// An iterator visiting the counted keys and their probabilities in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, f64)> {
+        self.probs.iter().map(|(item, &prob)| (item, prob))
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: ToPrimitive,
+{
+    /// Returns the maximum likelihood [`Distribution`] of the counts.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "aaab".chars().collect::<Counter<_>>();
+    /// assert_eq!(counter.distribution().prob(&'a'), 0.75);
+    /// ```
+    pub fn distribution(&self) -> Distribution<T> {
+        Distribution::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-12;
+
+    fn total_probability<T: Hash + Eq>(dist: &Distribution<T>) -> f64 {
+        let unseen_keys = (dist.vocab_size() - dist.len()) as f64;
+        dist.iter().map(|(_, prob)| prob).sum::<f64>() + unseen_keys * dist.unseen_probability()
+    }
+
+    fn corpus() -> Counter<&'static str> {
+        "a a a a b b b c c d e f g g h".split_whitespace().collect()
+    }
+
+    #[test]
+    fn test_maximum_likelihood() {
+        let dist = Distribution::new(&corpus());
+        assert_eq!(dist.vocab_size(), 8);
+        assert_eq!(dist.prob("a"), 4.0 / 15.0);
+        assert_eq!(dist.prob("z"), 0.0);
+        assert!((total_probability(&dist) - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_add_k() {
+        let dist = Distribution::smoothed(&corpus(), Smoothing::Lidstone(0.5), 12);
+        assert_eq!(dist.prob("a"), 4.5 / 21.0);
+        assert_eq!(dist.prob("z"), 0.5 / 21.0);
+        assert!((total_probability(&dist) - 1.0).abs() < EPSILON);
+
+        let laplace = Distribution::smoothed(&corpus(), Smoothing::Laplace, 12);
+        let lidstone = Distribution::smoothed(&corpus(), Smoothing::Lidstone(1.0), 12);
+        assert_eq!(laplace.prob("h"), lidstone.prob("h"));
+        assert_eq!(laplace.unseen_probability(), lidstone.unseen_probability());
+    }
+
+    #[test]
+    fn test_good_turing() {
+        // Counts of counts: n(1) = 4, n(2) = 2, n(3) = 1, n(4) = 1.
+        let dist = Distribution::smoothed(&corpus(), Smoothing::GoodTuring, 10);
+        assert!((dist.unseen_probability() - 4.0 / 15.0 / 2.0).abs() < EPSILON);
+        assert!((total_probability(&dist) - 1.0).abs() < EPSILON);
+        // Singletons are adjusted to 2 * n(2) / n(1) = 1, doubletons to 3 * n(3) / n(2) = 1.5.
+        assert!((dist.prob("c") / dist.prob("d") - 1.5).abs() < EPSILON);
+        assert!(dist.prob("d") < 1.0 / 15.0);
+    }
+
+    #[test]
+    fn test_good_turing_rounds_counts() {
+        // "a" rounds to zero, so it is unseen along with the two keys outside the counter.
+        let counter = vec![("a", 0.25), ("b", 0.75), ("c", 2.4)]
+            .into_iter()
+            .collect::<Counter<_, f64>>();
+        let dist = Distribution::smoothed(&counter, Smoothing::GoodTuring, 5);
+        assert_eq!(dist.len(), 2);
+        assert!((dist.unseen_probability() - 1.0 / 9.0).abs() < EPSILON);
+        assert_eq!(dist.prob("a"), dist.unseen_probability());
+        assert!((dist.prob("b") - 1.0 / 3.0).abs() < EPSILON);
+        assert!((total_probability(&dist) - 1.0).abs() < EPSILON);
+
+        let whole = vec![("b", 1), ("c", 2)]
+            .into_iter()
+            .collect::<Counter<&str>>();
+        let expected = Distribution::smoothed(&whole, Smoothing::GoodTuring, 5);
+        assert_eq!(dist.prob("b"), expected.prob("b"));
+        assert_eq!(dist.prob("c"), expected.prob("c"));
+        assert_eq!(dist.unseen_probability(), expected.unseen_probability());
+    }
+
+    #[test]
+    fn test_vocabulary_fully_counted() {
+        let dist = Distribution::smoothed(&corpus(), Smoothing::GoodTuring, 0);
+        assert_eq!(dist.unseen_probability(), 0.0);
+        assert!((total_probability(&dist) - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_with_unseen_probability() {
+        let dist =
+            Distribution::smoothed(&corpus(), Smoothing::Laplace, 18).with_unseen_probability(0.01);
+        assert_eq!(dist.prob("z"), 0.01);
+        assert!((total_probability(&dist) - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_unseen_probability_too_large() {
+        let _ = Distribution::smoothed(&corpus(), Smoothing::None, 18).with_unseen_probability(0.2);
+    }
+
+    #[test]
+    fn test_empty() {
+        let counter = Counter::<char>::new();
+        let dist = Distribution::smoothed(&counter, Smoothing::Laplace, 4);
+        assert_eq!(dist.prob(&'a'), 0.25);
+        let dist = Distribution::smoothed(&counter, Smoothing::GoodTuring, 4);
+        assert_eq!(dist.prob(&'a'), 0.0);
+    }
+}
@@ -0,0 +1,143 @@
+//! Iterating over a counter as a multiset.
+
+use crate::Counter;
+
+use num_traits::{One, Zero};
+
+use std::collections::hash_map;
+use std::hash::Hash;
+use std::iter::FusedIterator;
+use std::ops::SubAssign;
+
+/// An iterator over the elements of a counter, repeating each one as many times as its count.
+///
+/// This `struct` is created by [`Counter::elements`] and [`Counter::elements_sorted`]. See their
+/// documentation for more.
+#[derive(Clone, Debug)]
+pub struct Elements<'a, T, N, I = hash_map::Iter<'a, T, N>> {
+    iter: I,
+    current: Option<(&'a T, N)>,
+}
+
+impl<'a, T, N, I> Elements<'a, T, N, I> {
+    fn new(iter: I) -> Self {
+        Elements {
+            iter,
+            current: None,
+        }
+    }
+}
+
+impl<'a, T, N: 'a, I> Iterator for Elements<'a, T, N, I>
+where
+    I: Iterator<Item = (&'a T, &'a N)>,
+    N: Clone + PartialOrd + SubAssign + Zero + One,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            if let Some((item, remaining)) = &mut self.current {
+                if *remaining > N::zero() {
+                    *remaining -= N::one();
+                    return Some(*item);
+                }
+            }
+            let (item, count) = self.iter.next()?;
+            self.current = Some((item, count.clone()));
+        }
+    }
+}
+
+impl<'a, T, N: 'a, I> FusedIterator for Elements<'a, T, N, I>
+where
+    I: FusedIterator<Item = (&'a T, &'a N)>,
+    N: Clone + PartialOrd + SubAssign + Zero + One,
+{
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Clone + PartialOrd + SubAssign + Zero + One,
+{
+    /// Returns an iterator over the elements, repeating each one as many times as its count,
+    /// like Python's `Counter.elements()`.
+    ///
+    /// Elements are visited in arbitrary order, with all the repetitions of an element next to
+    /// each other. Elements whose count is not positive are skipped.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// let elements = counter.elements().cloned().collect::<Vec<_>>();
+    /// assert_eq!(elements.len(), 11);
+    /// assert_eq!(elements.into_iter().collect::<Counter<_>>(), counter);
+    /// ```
+    pub fn elements(&self) -> Elements<'_, T, N> {
+        Elements::new(self.map.iter())
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Ord,
+    N: Clone + PartialOrd + SubAssign + Zero + One,
+{
+    /// Returns an iterator over the elements in sorted order, repeating each one as many times
+    /// as its count.
+    ///
+    /// Elements whose count is not positive are skipped.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// let elements = counter.elements_sorted().collect::<String>();
+    /// assert_eq!(elements, "aaaaabbcdrr");
+    /// ```
+    pub fn elements_sorted(&self) -> Elements<'_, T, N, std::vec::IntoIter<(&T, &N)>> {
+        let mut items = self.map.iter().collect::<Vec<_>>();
+        items.sort_unstable_by_key(|&(item, _)| item);
+        Elements::new(items.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Counter;
+
+    #[test]
+    fn test_elements_round_trip() {
+        let counter = vec![(3, 4), (1, 1), (7, 2), (5, 3)]
+            .into_iter()
+            .collect::<Counter<u32>>();
+        let elements = counter.elements().cloned().collect::<Vec<_>>();
+        assert_eq!(elements.len(), 10);
+        assert_eq!(elements.iter().cloned().collect::<Counter<_>>(), counter);
+
+        // All the repetitions of an element are next to each other.
+        let mut runs = elements.clone();
+        runs.dedup();
+        assert_eq!(runs.len(), counter.len());
+
+        assert_eq!(
+            counter.elements_sorted().cloned().collect::<Vec<_>>(),
+            vec![1, 3, 3, 3, 3, 5, 5, 5, 7, 7]
+        );
+    }
+
+    #[test]
+    fn test_elements_skips_non_positive_counts() {
+        let counter = vec![('a', 2), ('b', 0), ('c', -3), ('d', 1)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        assert_eq!(counter.elements_sorted().collect::<String>(), "aad");
+    }
+
+    #[test]
+    fn test_elements_empty() {
+        let counter = Counter::<char>::new();
+        assert_eq!(counter.elements().next(), None);
+        assert_eq!(counter.elements_sorted().next(), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Frequencies of frequencies: how many items were counted once, how many twice, and so on.
+
+use crate::Counter;
+
+use num_traits::One;
+
+use std::hash::Hash;
+use std::ops::Add;
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Hash + Eq + Clone,
+{
+    /// Returns a counter of the counts, mapping each count to the number of items which have it.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// let counts_of_counts = counter.counts_of_counts();
+    /// let expect = [(5, 1), (2, 2), (1, 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(counts_of_counts.into_map(), expect);
+    /// ```
+    pub fn counts_of_counts(&self) -> Counter<N, usize> {
+        self.map.values().cloned().collect()
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialEq + One + Add<Output = N>,
+{
+    /// Returns the number of items counted exactly once.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// assert_eq!(counter.singletons(), 2);
+    /// ```
+    pub fn singletons(&self) -> usize {
+        self.items_with_count(N::one())
+    }
+
+    /// Returns the number of items counted exactly twice.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// assert_eq!(counter.doubletons(), 2);
+    /// ```
+    pub fn doubletons(&self) -> usize {
+        self.items_with_count(N::one() + N::one())
+    }
+
+    /// Returns the fraction of the items which were counted exactly once, their [hapax
+    /// legomena].
+    ///
+    /// An empty counter has a ratio of zero.
+    ///
+    /// [hapax legomena]: https://en.wikipedia.org/wiki/Hapax_legomenon
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// assert_eq!(counter.hapax_ratio(), 2.0 / 5.0);
+    /// ```
+    pub fn hapax_ratio(&self) -> f64 {
+        if self.map.is_empty() {
+            return 0.0;
+        }
+        self.singletons() as f64 / self.map.len() as f64
+    }
+
+    fn items_with_count(&self, count: N) -> usize {
+        self.map.values().filter(|&other| *other == count).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Counter;
+    use maplit::hashmap;
+
+    #[test]
+    fn test_counts_of_counts() {
+        let counter = vec![
+            ("apple", 3),
+            ("fig", 1),
+            ("kiwi", 1),
+            ("lime", 5),
+            ("pear", 1),
+            ("plum", 2),
+        ]
+        .into_iter()
+        .collect::<Counter<&str>>();
+        let counts_of_counts = counter.counts_of_counts();
+        let expected = hashmap! {
+            1 => 3,
+            2 => 1,
+            3 => 1,
+            5 => 1,
+        };
+        assert_eq!(counts_of_counts.clone().into_map(), expected);
+        assert_eq!(counts_of_counts.total::<usize>(), counter.len());
+        assert_eq!(counter.singletons(), 3);
+        assert_eq!(counter.doubletons(), 1);
+        assert_eq!(counter.hapax_ratio(), 0.5);
+    }
+
+    #[test]
+    fn test_empty() {
+        let counter = Counter::<char>::new();
+        assert!(counter.counts_of_counts().is_empty());
+        assert_eq!(counter.singletons(), 0);
+        assert_eq!(counter.doubletons(), 0);
+        assert_eq!(counter.hapax_ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_signed_counts() {
+        let counter = vec![('a', 1), ('b', -1), ('c', 2), ('d', 1)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        assert_eq!(counter.counts_of_counts()[&-1], 1);
+        assert_eq!(counter.singletons(), 2);
+        assert_eq!(counter.doubletons(), 1);
+        assert_eq!(counter.hapax_ratio(), 0.5);
+    }
+}
@@ -0,0 +1,312 @@
+//! Bounded-memory tracking of the most common items of a stream.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::Hash;
+
+/// Tracks the most common items of an unbounded stream in a fixed amount of memory, using the
+/// [Space-Saving] algorithm.
+///
+/// At most `capacity` items are tracked at once. When a new item arrives and the tracker is
+/// full, it takes the place of the item with the smallest count, inheriting that count as its
+/// possible error. Consequently, every count reported by [`estimate()`] is an upper bound of the
+/// true count, and exceeds it by at most [`error()`]. Any item which makes up more than
+/// 1 / `capacity` of the [`total()`] is guaranteed to be tracked.
+///
+/// [Space-Saving]: https://doi.org/10.1007/978-3-540-30570-5_27
+/// [`estimate()`]: HeavyHitters::estimate
+/// [`error()`]: HeavyHitters::error
+/// [`total()`]: HeavyHitters::total
+///
+/// ```rust
+/// # use counter::HeavyHitters;
+/// let mut hitters = HeavyHitters::new(3);
+/// hitters.update("abacabadabacaba".chars());
+/// assert_eq!(hitters.top(1), vec![('a', 8)]);
+/// assert_eq!(hitters.error(&'a'), 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HeavyHitters<T> {
+    capacity: usize,
+    slots: Vec<Slot<T>>,
+    index: HashMap<T, usize>,
+    // `(count, slot)` for every slot, so that the least common item can be found quickly.
+    by_count: BTreeSet<(usize, usize)>,
+    total: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Slot<T> {
+    item: T,
+    count: usize,
+    error: usize,
+}
+
+impl<T> HeavyHitters<T>
+where
+    T: Hash + Eq,
+{
+    /// Create a new, empty tracker which keeps the counts of at most `capacity` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> HeavyHitters<T> {
+        assert!(
+            capacity > 0,
+            "HeavyHitters needs a capacity of at least one"
+        );
+        HeavyHitters {
+            capacity,
+            slots: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+            by_count: BTreeSet::new(),
+            total: 0,
+        }
+    }
+
+    /// Returns the maximum number of items which are tracked at once.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of items which are currently tracked.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if no items have been counted yet.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Returns the sum of all the counts added to the tracker.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the smallest count of the tracked items, if the tracker is full, or zero.
+    ///
+    /// This bounds the true count of every item which is not tracked.
+    fn floor(&self) -> usize {
+        if self.slots.len() < self.capacity {
+            0
+        } else {
+            self.by_count.iter().next().map_or(0, |&(count, _)| count)
+        }
+    }
+
+    /// Returns an upper bound of the count of `key`.
+    ///
+    /// For tracked items, this is their count. Items which are not tracked may have occurred as
+    /// many times as the least common tracked item, once the tracker is full.
+    pub fn estimate<Q>(&self, key: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.index.get(key) {
+            Some(&slot) => self.slots[slot].count,
+            None => self.floor(),
+        }
+    }
+
+    /// Returns by how much [`estimate()`] may exceed the true count of `key`.
+    ///
+    /// [`estimate()`]: HeavyHitters::estimate
+    pub fn error<Q>(&self, key: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.index.get(key) {
+            Some(&slot) => self.slots[slot].error,
+            None => self.floor(),
+        }
+    }
+
+    /// Returns a lower bound of the count of `key`, which is guaranteed to have occurred at least
+    /// this many times.
+    ///
+    /// ```rust
+    /// # use counter::HeavyHitters;
+    /// let mut hitters = HeavyHitters::new(2);
+    /// hitters.update("aaabcd".chars());
+    /// assert_eq!(hitters.guaranteed(&'a'), 3);
+    /// assert_eq!(hitters.estimate(&'d'), 3);
+    /// assert_eq!(hitters.guaranteed(&'d'), 1);
+    /// ```
+    pub fn guaranteed<Q>(&self, key: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.estimate(key) - self.error(key)
+    }
+}
+
+impl<T> HeavyHitters<T>
+where
+    T: Hash + Eq + Clone,
+{
+    /// Add one to the count of `key`.
+    pub fn increment<Q>(&mut self, key: &Q)
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = T> + ?Sized,
+    {
+        self.increment_by(key, 1);
+    }
+
+    /// Add `count` to the count of `key`.
+    ///
+    /// If `key` is not tracked and the tracker is full, `key` replaces the least common tracked
+    /// item.
+    pub fn increment_by<Q>(&mut self, key: &Q, count: usize)
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = T> + ?Sized,
+    {
+        self.total += count;
+        if let Some(&slot) = self.index.get(key) {
+            self.raise(slot, count);
+        } else if self.slots.len() < self.capacity {
+            let slot = self.slots.len();
+            self.slots.push(Slot {
+                item: key.to_owned(),
+                count,
+                error: 0,
+            });
+            self.index.insert(key.to_owned(), slot);
+            self.by_count.insert((count, slot));
+        } else {
+            let (floor, slot) = *self.by_count.iter().next().expect("the tracker is full");
+            let evicted = std::mem::replace(&mut self.slots[slot].item, key.to_owned());
+            self.index.remove::<T>(&evicted);
+            self.index.insert(key.to_owned(), slot);
+            self.slots[slot].error = floor;
+            self.raise(slot, count);
+        }
+    }
+
+    /// Add `count` to the count of the item in `slot`.
+    fn raise(&mut self, slot: usize, count: usize) {
+        let entry = &mut self.slots[slot];
+        self.by_count.remove(&(entry.count, slot));
+        entry.count += count;
+        self.by_count.insert((entry.count, slot));
+    }
+
+    /// Add the counts of the elements from the given iterable to this tracker.
+    pub fn update<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            self.increment(&item);
+        }
+    }
+
+    /// Returns the `k` items with the highest estimated counts, as `(elem, frequency)` pairs
+    /// sorted most to least common.
+    ///
+    /// The ordering of duplicates is unspecified. Use [`guaranteed()`] to tell whether an item
+    /// is certain to belong in the result.
+    ///
+    /// [`guaranteed()`]: HeavyHitters::guaranteed
+    pub fn top(&self, k: usize) -> Vec<(T, usize)> {
+        self.by_count
+            .iter()
+            .rev()
+            .take(k)
+            .map(|&(count, slot)| (self.slots[slot].item.clone(), count))
+            .collect()
+    }
+}
+
+impl<T> Extend<T> for HeavyHitters<T>
+where
+    T: Hash + Eq + Clone,
+{
+    /// Extend a tracker with an iterator of items.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.update(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Counter;
+
+    #[test]
+    fn test_exact_below_capacity() {
+        let mut hitters = HeavyHitters::new(5);
+        hitters.update("abracadabra".chars());
+        let counter = Counter::<_>::init("abracadabra".chars());
+        assert_eq!(hitters.len(), 5);
+        for (key, &count) in counter.iter() {
+            assert_eq!(hitters.estimate(key), count);
+            assert_eq!(hitters.error(key), 0);
+        }
+        let mut top = hitters.top(5);
+        top.sort_by(|(a_item, a_count), (b_item, b_count)| {
+            b_count.cmp(a_count).then(a_item.cmp(b_item))
+        });
+        assert_eq!(top, counter.most_common_ordered());
+    }
+
+    #[test]
+    fn test_bounds_hold() {
+        // A skewed stream: small values are much more common than large ones. Stepping by the
+        // golden ratio spreads `x` evenly over [0, 1) without repeating a pattern.
+        let values: Vec<u32> = (0..20_000)
+            .map(|i| {
+                let x = (f64::from(i) * 0.618_033_988_749_895).fract();
+                (x * x * x * 1000.0) as u32
+            })
+            .collect();
+
+        let mut hitters = HeavyHitters::new(50);
+        hitters.update(values.iter().cloned());
+        let counter: Counter<_> = values.into_iter().collect();
+
+        assert_eq!(hitters.total(), 20_000);
+        assert_eq!(hitters.len(), 50);
+        for (key, &count) in counter.iter() {
+            assert!(hitters.guaranteed(key) <= count);
+            assert!(count <= hitters.estimate(key));
+        }
+        // Every item with more than total / capacity occurrences must be tracked.
+        for (key, &count) in counter.iter() {
+            if count > hitters.total() / hitters.capacity() {
+                assert!(hitters.top(50).iter().any(|(item, _)| item == key));
+            }
+        }
+        assert_eq!(hitters.top(1)[0].0, counter.k_most_common_ordered(1)[0].0);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let mut hitters = HeavyHitters::<String>::new(2);
+        hitters.increment_by("a", 5);
+        hitters.increment_by("b", 2);
+        hitters.increment("c");
+        // "b" was evicted, so it may have occurred as often as the least common tracked item.
+        assert_eq!(hitters.estimate("b"), 3);
+        assert_eq!(hitters.guaranteed("b"), 0);
+        assert_eq!(hitters.estimate("c"), 3);
+        assert_eq!(hitters.error("c"), 2);
+        assert_eq!(hitters.guaranteed("c"), 1);
+        assert_eq!(
+            hitters.top(2),
+            vec![("a".to_string(), 5), ("c".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_capacity() {
+        let _ = HeavyHitters::<char>::new(0);
+    }
+}
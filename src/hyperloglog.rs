@@ -0,0 +1,521 @@
+//! Estimating the number of distinct items without storing them.
+
+use crate::count_min::SketchState;
+use crate::{hash_one, Counter};
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, BitOr, BitOrAssign};
+
+/// A [HyperLogLog] sketch, which estimates the number of distinct items it has seen in a fixed
+/// amount of memory.
+///
+/// The sketch keeps 2<sup>`precision`</sup> one-byte registers. Its estimates have a relative
+/// standard error of about 1.04 / √(2<sup>`precision`</sup>), so a precision of 14 uses 16 KiB
+/// and has a standard error of about 0.8%; about two thirds of estimates are within that of the
+/// true number of distinct items, and nearly all are within three times that. The estimates are
+/// unbiased at every cardinality, and for cardinalities much smaller than the number of registers
+/// they are nearly exact.
+///
+/// Sketches with the same precision hash items identically, so they can be merged with `|` or
+/// `+`; both produce the sketch of the union.
+///
+/// [HyperLogLog]: https://en.wikipedia.org/wiki/HyperLogLog
+///
+/// ```rust
+/// # use counter::HyperLogLog;
+/// let mut sketch = HyperLogLog::new(12);
+/// sketch.update((0..10_000).map(|i| i % 1000));
+/// let estimate = sketch.estimate();
+/// assert!(950 <= estimate && estimate <= 1050);
+/// ```
+pub struct HyperLogLog<T: ?Sized> {
+    precision: u8,
+    registers: Vec<u8>,
+    item: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized> HyperLogLog<T> {
+    /// The smallest supported precision.
+    pub const MIN_PRECISION: u8 = 4;
+    /// The largest supported precision.
+    pub const MAX_PRECISION: u8 = 16;
+
+    /// Create a new, empty sketch with 2<sup>`precision`</sup> registers.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `precision` lies between [`MIN_PRECISION`] and [`MAX_PRECISION`].
+    ///
+    /// [`MIN_PRECISION`]: HyperLogLog::MIN_PRECISION
+    /// [`MAX_PRECISION`]: HyperLogLog::MAX_PRECISION
+    pub fn new(precision: u8) -> HyperLogLog<T> {
+        check_precision(precision);
+        HyperLogLog {
+            precision,
+            registers: vec![0; 1 << precision],
+            item: PhantomData,
+        }
+    }
+
+    /// Returns the precision of the sketch.
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Returns `true` if no items have been added to the sketch.
+    pub fn is_empty(&self) -> bool {
+        self.registers.iter().all(|&register| register == 0)
+    }
+
+    /// Returns the estimated number of distinct items added to the sketch.
+    pub fn estimate(&self) -> usize {
+        // Otmar Ertl's improved estimator, from "New cardinality estimation algorithms for
+        // HyperLogLog sketches" (2017). Unlike the original estimator, it needs neither a switch
+        // to linear counting for small cardinalities nor empirical bias corrections: it is close
+        // to unbiased over the whole range.
+        let m = self.registers.len() as f64;
+        let q = 64 - usize::from(self.precision);
+        let mut histogram = vec![0usize; q + 2];
+        for &register in &self.registers {
+            histogram[usize::from(register)] += 1;
+        }
+
+        let mut z = m * tau(1.0 - histogram[q + 1] as f64 / m);
+        for &count in histogram[1..=q].iter().rev() {
+            z = 0.5 * (z + count as f64);
+        }
+        z += m * sigma(histogram[0] as f64 / m);
+        (m * m / (2.0 * std::f64::consts::LN_2 * z)).round() as usize
+    }
+
+    fn merge(&mut self, other: &HyperLogLog<T>) {
+        assert_eq!(
+            self.precision, other.precision,
+            "cannot merge sketches with different precisions"
+        );
+        for (register, &other_register) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(other_register);
+        }
+    }
+}
+
+/// Ertl's σ function, for the registers which are still empty.
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+    let mut y = 1.0;
+    let mut z = x;
+    loop {
+        x *= x;
+        let previous = z;
+        z += x * y;
+        y += y;
+        if z == previous {
+            return z;
+        }
+    }
+}
+
+/// Ertl's τ function, for the registers which have saturated.
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+    let mut y = 1.0;
+    let mut z = 1.0 - x;
+    loop {
+        x = x.sqrt();
+        let previous = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if z == previous {
+            return z / 3.0;
+        }
+    }
+}
+
+fn check_precision(precision: u8) {
+    assert!(
+        (HyperLogLog::<()>::MIN_PRECISION..=HyperLogLog::<()>::MAX_PRECISION).contains(&precision),
+        "a HyperLogLog needs a precision between {} and {}",
+        HyperLogLog::<()>::MIN_PRECISION,
+        HyperLogLog::<()>::MAX_PRECISION
+    );
+}
+
+impl<T> HyperLogLog<T>
+where
+    T: Hash + ?Sized,
+{
+    /// Add `key` to the sketch.
+    pub fn insert<Q>(&mut self, key: &Q)
+    where
+        T: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        let hash = hash_one(&SketchState::default(), key);
+        let precision = u32::from(self.precision);
+        // The top bits pick a register, which records the longest run of leading zeros seen in
+        // the remaining bits. The sentinel bit caps the run for hashes whose remaining bits are
+        // all zero.
+        let index = (hash >> (64 - precision)) as usize;
+        let rest = (hash << precision) | (1 << (precision - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+        let register = &mut self.registers[index];
+        *register = (*register).max(rank);
+    }
+}
+
+impl<T> HyperLogLog<T>
+where
+    T: Hash,
+{
+    /// Add the elements from the given iterable to this sketch.
+    pub fn update<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            self.insert(&item);
+        }
+    }
+}
+
+impl<T: ?Sized> Clone for HyperLogLog<T> {
+    fn clone(&self) -> Self {
+        HyperLogLog {
+            precision: self.precision,
+            registers: self.registers.clone(),
+            item: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for HyperLogLog<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HyperLogLog")
+            .field("precision", &self.precision)
+            .field("estimate", &self.estimate())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> BitOrAssign for HyperLogLog<T> {
+    /// Merge another sketch into this one.
+    ///
+    /// The result is the same as if all the items added to `rhs` had been added to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two sketches have different precisions.
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.merge(&rhs);
+    }
+}
+
+impl<T: ?Sized> BitOr for HyperLogLog<T> {
+    type Output = HyperLogLog<T>;
+
+    /// Merge two sketches into the sketch of their union.
+    ///
+    /// ```rust
+    /// # use counter::HyperLogLog;
+    /// let mut a = HyperLogLog::new(10);
+    /// a.update(0..100);
+    /// let mut b = HyperLogLog::new(10);
+    /// b.update(50..150);
+    ///
+    /// let c = a | b;
+    /// assert!((145..=155).contains(&c.estimate()));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two sketches have different precisions.
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl<T: ?Sized> AddAssign for HyperLogLog<T> {
+    /// Merge another sketch into this one. This is the same as `|=`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two sketches have different precisions.
+    fn add_assign(&mut self, rhs: Self) {
+        self.merge(&rhs);
+    }
+}
+
+impl<T: ?Sized> Add for HyperLogLog<T> {
+    type Output = HyperLogLog<T>;
+
+    /// Merge two sketches into the sketch of their union. This is the same as `|`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two sketches have different precisions.
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T> Extend<T> for HyperLogLog<T>
+where
+    T: Hash,
+{
+    /// Extend a sketch with an iterator of items.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.update(iter);
+    }
+}
+
+/// Counts items exactly until there are too many distinct ones, and then only estimates how many
+/// distinct items there are.
+///
+/// A `DistinctCounter` starts out as an ordinary [`Counter`]. Once it holds more than
+/// `threshold` distinct items, it moves all of them into a [`HyperLogLog`] sketch and drops the
+/// counts, so that its memory use stays bounded no matter how long the stream runs.
+///
+/// ```rust
+/// # use counter::DistinctCounter;
+/// let mut distinct = DistinctCounter::new(100, 12);
+/// distinct.update("abracadabra".chars());
+/// assert_eq!(distinct.len(), 5);
+/// assert_eq!(distinct.counter().unwrap()[&'a'], 5);
+///
+/// distinct.update((0..1000).map(|i| char::from_u32(0x4e00 + i).unwrap()));
+/// assert!(!distinct.is_exact());
+/// assert!((980..=1030).contains(&distinct.len()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DistinctCounter<T: Hash + Eq> {
+    threshold: usize,
+    precision: u8,
+    state: Distinct<T>,
+}
+
+#[derive(Clone, Debug)]
+enum Distinct<T: Hash + Eq> {
+    Exact(Counter<T>),
+    Approximate(HyperLogLog<T>),
+}
+
+impl<T> DistinctCounter<T>
+where
+    T: Hash + Eq,
+{
+    /// Create a new, empty `DistinctCounter` which counts exactly up to `threshold` distinct
+    /// items, and then switches to a [`HyperLogLog`] sketch with the given `precision`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `precision` is not supported by [`HyperLogLog::new`].
+    pub fn new(threshold: usize, precision: u8) -> DistinctCounter<T> {
+        check_precision(precision);
+        DistinctCounter {
+            threshold,
+            precision,
+            state: Distinct::Exact(Counter::new()),
+        }
+    }
+
+    /// Returns the number of distinct items which can be counted exactly.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns `true` while the items are still counted exactly.
+    pub fn is_exact(&self) -> bool {
+        matches!(self.state, Distinct::Exact(_))
+    }
+
+    /// Returns the exact counts, unless the threshold has been exceeded.
+    pub fn counter(&self) -> Option<&Counter<T>> {
+        match &self.state {
+            Distinct::Exact(counter) => Some(counter),
+            Distinct::Approximate(_) => None,
+        }
+    }
+
+    /// Returns the number of distinct items seen so far, which is exact while [`is_exact()`]
+    /// holds and estimated afterwards.
+    ///
+    /// [`is_exact()`]: DistinctCounter::is_exact
+    pub fn len(&self) -> usize {
+        match &self.state {
+            Distinct::Exact(counter) => counter.len(),
+            Distinct::Approximate(sketch) => sketch.estimate(),
+        }
+    }
+
+    /// Returns `true` if no items have been seen.
+    pub fn is_empty(&self) -> bool {
+        match &self.state {
+            Distinct::Exact(counter) => counter.is_empty(),
+            Distinct::Approximate(sketch) => sketch.is_empty(),
+        }
+    }
+
+    /// Count one occurrence of `item`.
+    pub fn insert(&mut self, item: T) {
+        match &mut self.state {
+            Distinct::Exact(counter) => {
+                *counter.entry(item).or_insert(0) += 1;
+                if counter.len() > self.threshold {
+                    let mut sketch = HyperLogLog::new(self.precision);
+                    for key in counter.keys() {
+                        sketch.insert(key);
+                    }
+                    self.state = Distinct::Approximate(sketch);
+                }
+            }
+            Distinct::Approximate(sketch) => sketch.insert(&item),
+        }
+    }
+
+    /// Count the elements from the given iterable.
+    pub fn update<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            self.insert(item);
+        }
+    }
+}
+
+impl<T> Extend<T> for DistinctCounter<T>
+where
+    T: Hash + Eq,
+{
+    /// Extend a `DistinctCounter` with an iterator of items.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.update(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_cardinalities_are_nearly_exact() {
+        let mut sketch = HyperLogLog::<u32>::new(14);
+        assert!(sketch.is_empty());
+        assert_eq!(sketch.estimate(), 0);
+        for n in 1..=200u32 {
+            sketch.insert(&n);
+            sketch.insert(&n);
+            let estimate = sketch.estimate() as i64;
+            assert!(
+                (estimate - i64::from(n)).abs() <= 2,
+                "{} for {}",
+                estimate,
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_cardinalities() {
+        for &precision in &[4, 10, 16] {
+            let mut sketch = HyperLogLog::new(precision);
+            sketch.update(0..200_000u64);
+            let error = (sketch.estimate() as f64 - 200_000.0).abs() / 200_000.0;
+            let standard_error = 1.04 / f64::from(1u32 << precision).sqrt();
+            assert!(error < 4.0 * standard_error, "{} at {}", error, precision);
+        }
+    }
+
+    /// The original HyperLogLog estimator is biased where it hands over from linear counting,
+    /// around 2.5 times the number of registers; check that the mean error stays small there.
+    #[test]
+    fn test_crossover_cardinalities_are_unbiased() {
+        const TRIALS: u64 = 8;
+        for &precision in &[8, 12, 14] {
+            let registers = 1u64 << precision;
+            let standard_error = 1.04 / (registers as f64).sqrt();
+            for &multiple in &[1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0] {
+                let len = (multiple * registers as f64) as u64;
+                let mean_error = (0..TRIALS)
+                    .map(|trial| {
+                        let mut sketch = HyperLogLog::new(precision);
+                        sketch.update((0..len).map(|i| trial << 32 | i));
+                        let error = (sketch.estimate() as f64 - len as f64) / len as f64;
+                        assert!(error.abs() < 4.0 * standard_error);
+                        error
+                    })
+                    .sum::<f64>()
+                    / TRIALS as f64;
+                let tolerance = 3.0 * standard_error / (TRIALS as f64).sqrt();
+                assert!(
+                    mean_error.abs() < tolerance,
+                    "{} for {} at {}",
+                    mean_error,
+                    len,
+                    precision
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_is_union() {
+        let mut a = HyperLogLog::new(12);
+        a.update(0..30_000);
+        let mut b = HyperLogLog::new(12);
+        b.update(20_000..50_000);
+        let mut union = HyperLogLog::new(12);
+        union.update(0..50_000);
+
+        let merged = a.clone() | b.clone();
+        assert_eq!(merged.registers, union.registers);
+        assert_eq!((a + b).registers, union.registers);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_mismatched_precisions() {
+        let _ = HyperLogLog::<u32>::new(10) | HyperLogLog::new(11);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_precision_out_of_range() {
+        let _ = HyperLogLog::<u32>::new(17);
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        let mut a = HyperLogLog::<String>::new(8);
+        a.insert("able");
+        let mut b = HyperLogLog::<str>::new(8);
+        b.insert("able");
+        assert_eq!(a.registers, b.registers);
+    }
+
+    #[test]
+    fn test_distinct_counter_switches_at_threshold() {
+        let mut distinct = DistinctCounter::new(1000, 14);
+        assert!(distinct.is_empty());
+        distinct.update((0..1000).chain(0..1000));
+        assert!(distinct.is_exact());
+        assert_eq!(distinct.len(), 1000);
+        assert_eq!(distinct.counter().unwrap()[&7], 2);
+
+        distinct.insert(1000);
+        assert!(!distinct.is_exact());
+        assert!(distinct.counter().is_none());
+        distinct.update(0..20_000);
+        let error = (distinct.len() as f64 - 20_000.0).abs() / 20_000.0;
+        assert!(error < 0.05, "{}", error);
+    }
+}
@@ -0,0 +1,961 @@
+//! An insertion-ordered counter backed by an [`IndexMap`], enabled with the `indexmap` feature.
+//!
+//! [`IndexMap`]: https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html
+
+use crate::{k_smallest, Counter};
+
+use indexmap::IndexMap;
+use num_traits::{One, Zero};
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::iter;
+use std::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, Deref, DerefMut, Index, IndexMut,
+    Sub, SubAssign,
+};
+
+type IndexCounterMap<T, N, S> = IndexMap<T, N, S>;
+
+/// A counter which remembers the order in which its items were first seen.
+///
+/// `IndexCounter<T, N>` has the same interface as [`Counter`], but it is backed by an
+/// [`IndexMap`] instead of a [`HashMap`]. Like Python's `Counter`, it iterates in the order in
+/// which items were first counted, and [`most_common()`] breaks ties in that order too, so its
+/// results are reproducible from run to run.
+///
+/// Removing an item, for instance when [`subtract()`] takes its count to zero, preserves the
+/// order of the remaining items. Should the item be counted again later, it is then treated as
+/// newly seen.
+///
+/// [`IndexMap`]: https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html
+/// [`HashMap`]: https://doc.rust-lang.org/stable/std/collections/struct.HashMap.html
+/// [`most_common()`]: IndexCounter::most_common
+/// [`subtract()`]: IndexCounter::subtract
+///
+/// ```rust
+/// # use counter::IndexCounter;
+/// let counter = "abracadabra".chars().collect::<IndexCounter<_>>();
+/// assert_eq!(counter.keys().collect::<String>(), "abrcd");
+/// assert_eq!(
+///     counter.most_common(),
+///     vec![('a', 5), ('b', 2), ('r', 2), ('c', 1), ('d', 1)],
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct IndexCounter<T: Hash + Eq, N = usize, S = RandomState> {
+    map: IndexCounterMap<T, N, S>,
+    // necessary for `Index::index` since we cannot declare generic `static` variables.
+    zero: N,
+}
+
+impl<T, N, S> PartialEq for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialEq,
+    S: BuildHasher,
+{
+    /// Two counters are equal if they hold the same counts, regardless of their order.
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<T, N, S> Eq for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Eq,
+    S: BuildHasher,
+{
+}
+
+impl<T, N, S> IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+{
+    /// Consumes this counter and returns an [`IndexMap`] mapping the items to the counts.
+    ///
+    /// [`IndexMap`]: https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html
+    pub fn into_map(self) -> IndexMap<T, N, S> {
+        self.map
+    }
+
+    /// Returns the sum of the counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use counter::IndexCounter;
+    /// let counter = IndexCounter::init("abracadabra".chars());
+    /// assert_eq!(counter.total::<usize>(), 11);
+    /// assert_eq!(counter.len(), 5);
+    /// ```
+    pub fn total<'a, R>(&'a self) -> R
+    where
+        R: iter::Sum<&'a N>,
+    {
+        self.map.values().sum()
+    }
+}
+
+impl<T, N> IndexCounter<T, N>
+where
+    T: Hash + Eq,
+    N: Zero,
+{
+    /// Create a new, empty `IndexCounter`
+    pub fn new() -> IndexCounter<T, N> {
+        IndexCounter {
+            map: IndexMap::new(),
+            zero: N::zero(),
+        }
+    }
+
+    /// Create a new, empty `IndexCounter` with space for at least `capacity` distinct items.
+    pub fn with_capacity(capacity: usize) -> IndexCounter<T, N> {
+        IndexCounter {
+            map: IndexMap::with_capacity(capacity),
+            zero: N::zero(),
+        }
+    }
+}
+
+impl<T, N, S> IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Zero,
+    S: BuildHasher,
+{
+    /// Create a new, empty `IndexCounter` which will use the given hash builder to hash its
+    /// items.
+    pub fn with_hasher(hash_builder: S) -> IndexCounter<T, N, S> {
+        IndexCounter {
+            map: IndexMap::with_hasher(hash_builder),
+            zero: N::zero(),
+        }
+    }
+
+    /// Create a new, empty `IndexCounter` with space for at least `capacity` distinct items,
+    /// which will use the given hash builder to hash its items.
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> IndexCounter<T, N, S> {
+        IndexCounter {
+            map: IndexMap::with_capacity_and_hasher(capacity, hash_builder),
+            zero: N::zero(),
+        }
+    }
+}
+
+impl<T, N> IndexCounter<T, N>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero + One,
+{
+    /// Create a new `IndexCounter` initialized with the given iterable.
+    pub fn init<I>(iterable: I) -> IndexCounter<T, N>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut counter = IndexCounter::new();
+        counter.update(iterable);
+        counter
+    }
+}
+
+impl<T, N, S> IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero + One,
+    S: BuildHasher,
+{
+    /// Add the counts of the elements from the given iterable to this counter.
+    ///
+    /// Items which are not yet in the counter are appended after all the existing ones.
+    pub fn update<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            let entry = self.map.entry(item).or_insert_with(N::zero);
+            *entry += N::one();
+        }
+    }
+}
+
+impl<T, N, S> IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + SubAssign + Zero + One,
+    S: BuildHasher,
+{
+    /// Remove the counts of the elements from the given iterable to this counter.
+    ///
+    /// Non-positive counts are automatically removed, preserving the order of the remaining
+    /// items.
+    ///
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let mut counter = "cabbccc".chars().collect::<IndexCounter<_>>();
+    /// counter.subtract("abba".chars());
+    /// assert_eq!(counter.into_iter().collect::<Vec<_>>(), vec![('c', 4)]);
+    /// ```
+    pub fn subtract<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            let mut remove = false;
+            if let Some(entry) = self.map.get_mut(&item) {
+                if *entry > N::zero() {
+                    *entry -= N::one();
+                }
+                remove = *entry == N::zero();
+            }
+            if remove {
+                self.map.shift_remove(&item);
+            }
+        }
+    }
+}
+
+impl<T, N, S> IndexCounter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Clone + Ord,
+{
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common.
+    ///
+    /// Unlike [`Counter::most_common`], the ordering of duplicates is stable: items with the same
+    /// count are sorted in the order in which they were first seen.
+    ///
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let mc = "eaddbbccc".chars().collect::<IndexCounter<_>>().most_common();
+    /// let expected = vec![('c', 3), ('d', 2), ('b', 2), ('e', 1), ('a', 1)];
+    /// assert_eq!(mc, expected);
+    /// ```
+    pub fn most_common(&self) -> Vec<(T, N)> {
+        let mut items = self
+            .map
+            .iter()
+            .map(|(key, count)| (key.clone(), count.clone()))
+            .collect::<Vec<_>>();
+        // The map iterates in insertion order, so a stable sort leaves ties in that order.
+        items.sort_by(|(_, a_count), (_, b_count)| b_count.cmp(a_count));
+        items
+    }
+
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common.
+    ///
+    /// In the event that two keys have an equal frequency, use the supplied ordering function
+    /// to further arrange the results. Keys which the tiebreaker considers equal remain in the
+    /// order in which they were first seen.
+    ///
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let counter = "eaddbbccc".chars().collect::<IndexCounter<_>>();
+    /// let by_common = counter.most_common_tiebreaker(|&a, &b| a.cmp(&b));
+    /// let expected = vec![('c', 3), ('b', 2), ('d', 2), ('a', 1), ('e', 1)];
+    /// assert_eq!(by_common, expected);
+    /// ```
+    pub fn most_common_tiebreaker<F>(&self, mut tiebreaker: F) -> Vec<(T, N)>
+    where
+        F: FnMut(&T, &T) -> ::std::cmp::Ordering,
+    {
+        let mut items = self
+            .map
+            .iter()
+            .map(|(key, count)| (key.clone(), count.clone()))
+            .collect::<Vec<_>>();
+        items.sort_by(|(a_item, a_count), (b_item, b_count)| {
+            b_count
+                .cmp(a_count)
+                .then_with(|| tiebreaker(a_item, b_item))
+        });
+        items
+    }
+
+    /// Returns the `k` most common items in decreasing order of their counts.
+    ///
+    /// The returned vector is the same as would be obtained by calling [`most_common`] and then
+    /// truncating the result to length `k`. In particular, items with the same count are sorted
+    /// in the order in which they were first seen. See [`Counter::k_most_common_ordered`] for a
+    /// discussion of the time complexity.
+    ///
+    /// [`most_common`]: IndexCounter::most_common
+    ///
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let counter: IndexCounter<_> = "abracadabra".chars().collect();
+    /// let top4 = counter.k_most_common(4);
+    /// assert_eq!(top4, vec![('a', 5), ('b', 2), ('r', 2), ('c', 1)]);
+    /// ```
+    pub fn k_most_common(&self, k: usize) -> Vec<(T, N)> {
+        use std::cmp::Reverse;
+
+        if k == 0 {
+            return vec![];
+        }
+
+        if k >= self.map.len() {
+            return self.most_common();
+        }
+
+        // Break ties on the position of each item in the map, which is its insertion order, and
+        // look the key up again by that position once the top `k` items have been selected.
+        let items = self
+            .map
+            .values()
+            .enumerate()
+            .map(|(i, n)| (Reverse(n.clone()), i));
+        let heap = k_smallest(items, k);
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|(Reverse(n), i)| {
+                let (t, _) = self.map.get_index(i).expect("the index is in bounds");
+                (t.clone(), n)
+            })
+            .collect()
+    }
+}
+
+impl<T, N, S> IndexCounter<T, N, S>
+where
+    T: Hash + Eq + Clone + Ord,
+    N: Clone + Ord,
+{
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common.
+    ///
+    /// In the event that two keys have an equal frequency, use the natural ordering of the keys
+    /// to further sort the results.
+    ///
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let mc = "eaddbbccc".chars().collect::<IndexCounter<_>>().most_common_ordered();
+    /// let expect = vec![('c', 3), ('b', 2), ('d', 2), ('a', 1), ('e', 1)];
+    /// assert_eq!(mc, expect);
+    /// ```
+    pub fn most_common_ordered(&self) -> Vec<(T, N)> {
+        self.most_common_tiebreaker(Ord::cmp)
+    }
+
+    /// Returns the `k` most common items in decreasing order of their counts, breaking ties
+    /// with the natural ordering of the keys.
+    ///
+    /// This is the same as would be obtained by calling [`most_common_ordered`] and then
+    /// truncating the result to length `k`.
+    ///
+    /// [`most_common_ordered`]: IndexCounter::most_common_ordered
+    pub fn k_most_common_ordered(&self, k: usize) -> Vec<(T, N)> {
+        use std::cmp::Reverse;
+
+        if k == 0 {
+            return vec![];
+        }
+
+        if k >= self.map.len() {
+            return self.most_common_ordered();
+        }
+
+        let items = self.map.iter().map(|(t, n)| (Reverse(n.clone()), t));
+        let heap = k_smallest(items, k);
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|(Reverse(n), t)| (t.clone(), n))
+            .collect()
+    }
+}
+
+impl<T, N, S> IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + Zero,
+    S: BuildHasher,
+{
+    /// Test whether this counter is a superset of another counter.
+    ///
+    /// See [`Counter::is_superset`].
+    pub fn is_superset(&self, other: &Self) -> bool {
+        self.keys()
+            .chain(other.keys())
+            .all(|key| self[key] >= other[key])
+    }
+
+    /// Test whether this counter is a subset of another counter.
+    ///
+    /// See [`Counter::is_subset`].
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.keys()
+            .chain(other.keys())
+            .all(|key| self[key] <= other[key])
+    }
+}
+
+impl<T, N, S> Default for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Default,
+    S: Default,
+{
+    fn default() -> Self {
+        Self {
+            map: Default::default(),
+            zero: Default::default(),
+        }
+    }
+}
+
+impl<T, N, S> From<IndexCounter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero,
+    S: BuildHasher + Default,
+{
+    /// Move the items of an `IndexCounter` into a [`Counter`], forgetting their order.
+    fn from(counter: IndexCounter<T, N, S>) -> Self {
+        counter.map.into_iter().collect()
+    }
+}
+
+impl<T, N, S> AddAssign for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Zero + AddAssign,
+    S: BuildHasher,
+{
+    /// Add another counter to this counter.
+    ///
+    /// `c += d;` -> `c[x] += d[x]` for all `x`
+    ///
+    /// Items of `d` which are not in `c` are appended in the order of `d`.
+    fn add_assign(&mut self, rhs: Self) {
+        for (key, value) in rhs.map {
+            let entry = self.map.entry(key).or_insert_with(N::zero);
+            *entry += value;
+        }
+    }
+}
+
+impl<T, N, S> Add for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero,
+    S: BuildHasher,
+{
+    type Output = IndexCounter<T, N, S>;
+
+    /// Add two counters together.
+    ///
+    /// `out = c + d;` -> `out[x] == c[x] + d[x]` for all `x`
+    ///
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let c = "baaa".chars().collect::<IndexCounter<_>>();
+    /// let d = "cab".chars().collect::<IndexCounter<_>>();
+    ///
+    /// let e = c + d;
+    ///
+    /// assert_eq!(e.into_iter().collect::<Vec<_>>(), vec![('b', 2), ('a', 4), ('c', 1)]);
+    /// ```
+    fn add(mut self, rhs: IndexCounter<T, N, S>) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T, N, S> SubAssign for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + PartialEq + SubAssign + Zero,
+    S: BuildHasher,
+{
+    /// Subtract (keeping only positive values).
+    ///
+    /// `c -= d;` -> `c[x] -= d[x]` for all `x`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    fn sub_assign(&mut self, mut rhs: Self) {
+        self.map
+            .retain(|key, entry| match rhs.map.swap_remove(key) {
+                Some(value) => {
+                    if *entry >= value {
+                        *entry -= value;
+                        *entry != N::zero()
+                    } else {
+                        false
+                    }
+                }
+                None => true,
+            });
+    }
+}
+
+impl<T, N, S> Sub for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + PartialEq + SubAssign + Zero,
+    S: BuildHasher,
+{
+    type Output = IndexCounter<T, N, S>;
+
+    /// Subtract (keeping only positive values).
+    ///
+    /// `out = c - d;` -> `out[x] == c[x] - d[x]` for all `x`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let c = "baaa".chars().collect::<IndexCounter<_>>();
+    /// let d = "abb".chars().collect::<IndexCounter<_>>();
+    ///
+    /// let e = c - d;
+    ///
+    /// assert_eq!(e.into_iter().collect::<Vec<_>>(), vec![('a', 2)]);
+    /// ```
+    fn sub(mut self, rhs: IndexCounter<T, N, S>) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<T, N, S> BitAnd for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero,
+    S: BuildHasher,
+{
+    type Output = IndexCounter<T, N, S>;
+
+    /// Returns the intersection of `self` and `rhs` as a new `IndexCounter`, in the order of
+    /// `self`.
+    ///
+    /// `out = c & d;` -> `out[x] == min(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let c = "baaa".chars().collect::<IndexCounter<_>>();
+    /// let d = "abb".chars().collect::<IndexCounter<_>>();
+    ///
+    /// let e = c & d;
+    ///
+    /// assert_eq!(e.into_iter().collect::<Vec<_>>(), vec![('b', 1), ('a', 1)]);
+    /// ```
+    fn bitand(mut self, rhs: IndexCounter<T, N, S>) -> Self::Output {
+        self &= rhs;
+        self
+    }
+}
+
+impl<T, N, S> BitAndAssign for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero,
+    S: BuildHasher,
+{
+    /// Updates `self` with the intersection of `self` and `rhs`
+    ///
+    /// `c &= d;` -> `c[x] == min(c[x], d[x])`
+    fn bitand_assign(&mut self, mut rhs: IndexCounter<T, N, S>) {
+        self.map
+            .retain(|key, lhs_count| match rhs.map.swap_remove(key) {
+                Some(rhs_count) => {
+                    if rhs_count < *lhs_count {
+                        *lhs_count = rhs_count;
+                    }
+                    true
+                }
+                None => false,
+            });
+    }
+}
+
+impl<T, N, S> BitOr for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero,
+    S: BuildHasher,
+{
+    type Output = IndexCounter<T, N, S>;
+
+    /// Returns the union of `self` and `rhs` as a new `IndexCounter`.
+    ///
+    /// `out = c | d;` -> `out[x] == max(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let c = "baaa".chars().collect::<IndexCounter<_>>();
+    /// let d = "abbc".chars().collect::<IndexCounter<_>>();
+    ///
+    /// let e = c | d;
+    ///
+    /// assert_eq!(e.into_iter().collect::<Vec<_>>(), vec![('b', 2), ('a', 3), ('c', 1)]);
+    /// ```
+    fn bitor(mut self, rhs: IndexCounter<T, N, S>) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl<T, N, S> BitOrAssign for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero,
+    S: BuildHasher,
+{
+    /// Updates `self` with the union of `self` and `rhs`
+    ///
+    /// `c |= d;` -> `c[x] == max(c[x], d[x])`
+    fn bitor_assign(&mut self, rhs: IndexCounter<T, N, S>) {
+        for (key, rhs_value) in rhs.map {
+            let entry = self.map.entry(key).or_insert_with(N::zero);
+            // See `impl BitOr for Counter` for why a weak inequality is used here.
+            if rhs_value >= *entry {
+                *entry = rhs_value;
+            }
+        }
+    }
+}
+
+impl<T, N, S> Deref for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+{
+    type Target = IndexCounterMap<T, N, S>;
+    fn deref(&self) -> &IndexCounterMap<T, N, S> {
+        &self.map
+    }
+}
+
+impl<T, N, S> DerefMut for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+{
+    fn deref_mut(&mut self) -> &mut IndexCounterMap<T, N, S> {
+        &mut self.map
+    }
+}
+
+impl<'a, T, N, S> IntoIterator for &'a IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+{
+    type Item = (&'a T, &'a N);
+    type IntoIter = indexmap::map::Iter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.iter()
+    }
+}
+
+impl<T, N, S> IntoIterator for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+{
+    type Item = (T, N);
+    type IntoIter = indexmap::map::IntoIter<T, N>;
+
+    /// Consumes the `IndexCounter` to produce an iterator that owns the values it returns, in
+    /// the order in which they were first seen.
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, T, N, S> IntoIterator for &'a mut IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+{
+    type Item = (&'a T, &'a mut N);
+    type IntoIter = indexmap::map::IterMut<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.iter_mut()
+    }
+}
+
+impl<T, Q, N, S> Index<&'_ Q> for IndexCounter<T, N, S>
+where
+    T: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq,
+    N: Zero,
+    S: BuildHasher,
+{
+    type Output = N;
+
+    /// Index in immutable contexts.
+    ///
+    /// Returns a reference to a [`zero`] value for missing keys.
+    ///
+    /// [`zero`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```
+    /// # use counter::IndexCounter;
+    /// let counter = IndexCounter::<_>::init("aabbcc".chars());
+    /// assert_eq!(counter[&'a'], 2);
+    /// assert_eq!(counter[&'d'], 0);
+    /// ```
+    fn index(&self, key: &'_ Q) -> &N {
+        self.map.get(key).unwrap_or(&self.zero)
+    }
+}
+
+impl<T, Q, N, S> IndexMut<&'_ Q> for IndexCounter<T, N, S>
+where
+    T: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq + ToOwned<Owned = T>,
+    N: Zero,
+    S: BuildHasher,
+{
+    /// Index in mutable contexts.
+    ///
+    /// If the given key is not present, appends a new entry and initializes it with a [`zero`]
+    /// value.
+    ///
+    /// [`zero`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```
+    /// # use counter::IndexCounter;
+    /// let mut counter = IndexCounter::<_>::init("aabbcc".chars());
+    /// counter[&'c'] += 1;
+    /// counter[&'d'] += 1;
+    /// assert_eq!(counter[&'c'], 3);
+    /// assert_eq!(counter[&'d'], 1);
+    /// ```
+    fn index_mut(&mut self, key: &'_ Q) -> &mut N {
+        self.map.entry(key.to_owned()).or_insert_with(N::zero)
+    }
+}
+
+impl<I, T, N, S> AddAssign<I> for IndexCounter<T, N, S>
+where
+    I: IntoIterator<Item = T>,
+    T: Hash + Eq,
+    N: AddAssign + Zero + One,
+    S: BuildHasher,
+{
+    /// Directly add the counts of the elements of `I` to `self`.
+    fn add_assign(&mut self, rhs: I) {
+        self.update(rhs);
+    }
+}
+
+impl<I, T, N, S> Add<I> for IndexCounter<T, N, S>
+where
+    I: IntoIterator<Item = T>,
+    T: Hash + Eq,
+    N: AddAssign + Zero + One,
+    S: BuildHasher,
+{
+    type Output = Self;
+
+    /// Consume `self` producing an `IndexCounter` like `self` updated with the counts of
+    /// the elements of `I`.
+    fn add(mut self, rhs: I) -> Self::Output {
+        self.update(rhs);
+        self
+    }
+}
+
+impl<I, T, N, S> SubAssign<I> for IndexCounter<T, N, S>
+where
+    I: IntoIterator<Item = T>,
+    T: Hash + Eq,
+    N: PartialOrd + SubAssign + Zero + One,
+    S: BuildHasher,
+{
+    /// Directly subtract the counts of the elements of `I` from `self`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    fn sub_assign(&mut self, rhs: I) {
+        self.subtract(rhs);
+    }
+}
+
+impl<I, T, N, S> Sub<I> for IndexCounter<T, N, S>
+where
+    I: IntoIterator<Item = T>,
+    T: Hash + Eq,
+    N: PartialOrd + SubAssign + Zero + One,
+    S: BuildHasher,
+{
+    type Output = Self;
+
+    /// Consume `self` producing an `IndexCounter` like `self` with the counts of the
+    /// elements of `I` subtracted, keeping only positive values.
+    fn sub(mut self, rhs: I) -> Self::Output {
+        self.subtract(rhs);
+        self
+    }
+}
+
+impl<T, N, S> iter::FromIterator<T> for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero + One,
+    S: BuildHasher + Default,
+{
+    /// Produce an `IndexCounter` from an iterator of items. This is called automatically
+    /// by [`Iterator::collect()`].
+    ///
+    /// [`Iterator::collect()`]:
+    /// https://doc.rust-lang.org/stable/std/iter/trait.Iterator.html#method.collect
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = IndexCounter::with_hasher(S::default());
+        counter.update(iter);
+        counter
+    }
+}
+
+impl<T, N, S> iter::FromIterator<(T, N)> for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero,
+    S: BuildHasher + Default,
+{
+    /// Creates a counter from `(item, count)` tuples.
+    ///
+    /// The counts of duplicate items are summed.
+    fn from_iter<I: IntoIterator<Item = (T, N)>>(iter: I) -> Self {
+        let mut cnt = IndexCounter::with_hasher(S::default());
+        cnt.extend(iter);
+        cnt
+    }
+}
+
+impl<T, N, S> Extend<T> for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero + One,
+    S: BuildHasher,
+{
+    /// Extend an `IndexCounter` with an iterator of items.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.update(iter);
+    }
+}
+
+impl<T, N, S> Extend<(T, N)> for IndexCounter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero,
+    S: BuildHasher,
+{
+    /// Extend a counter with `(item, count)` tuples.
+    ///
+    /// The counts of duplicate items are summed.
+    fn extend<I: IntoIterator<Item = (T, N)>>(&mut self, iter: I) {
+        for (item, item_count) in iter {
+            let entry = self.map.entry(item).or_insert_with(N::zero);
+            *entry += item_count;
+        }
+    }
+}
+
+impl<'a, T: 'a, N: 'a, S> Extend<(&'a T, &'a N)> for IndexCounter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: AddAssign + Zero + Clone,
+    S: BuildHasher,
+{
+    /// Extend a counter with `(item, count)` tuples.
+    ///
+    /// You can extend an `IndexCounter` with another `IndexCounter`:
+    /// ```rust
+    /// # use counter::IndexCounter;
+    /// let mut counter = "abbccc".chars().collect::<IndexCounter<_>>();
+    /// let another = "dddccb".chars().collect::<IndexCounter<_>>();
+    /// counter.extend(&another);
+    /// let expect = vec![('a', 1), ('b', 3), ('c', 5), ('d', 3)];
+    /// assert_eq!(counter.into_iter().collect::<Vec<_>>(), expect);
+    /// ```
+    fn extend<I: IntoIterator<Item = (&'a T, &'a N)>>(&mut self, iter: I) {
+        for (item, item_count) in iter {
+            let entry = self.map.entry(item.clone()).or_insert_with(N::zero);
+            *entry += item_count.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_first_seen_order() {
+        let mut counter = IndexCounter::init("cbbaaa".chars());
+        counter.update("dc".chars());
+        let items: Vec<_> = counter.into_iter().collect();
+        assert_eq!(items, vec![('c', 2), ('b', 2), ('a', 3), ('d', 1)]);
+    }
+
+    #[test]
+    fn test_subtract_preserves_order() {
+        let mut counter = IndexCounter::<_>::init("dabbccc".chars());
+        counter.subtract("bbd".chars());
+        assert_eq!(counter.keys().collect::<String>(), "ac");
+        counter.update("bd".chars());
+        assert_eq!(counter.keys().collect::<String>(), "acbd");
+    }
+
+    #[test]
+    fn test_most_common_ties_in_first_seen_order() {
+        let counter = IndexCounter::<_>::init("edcbaabcde".chars());
+        let expected = vec![('e', 2), ('d', 2), ('c', 2), ('b', 2), ('a', 2)];
+        assert_eq!(counter.most_common(), expected);
+    }
+
+    #[test]
+    fn test_k_most_common() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let values: Vec<u32> = (0..200).map(|_| rng.gen_range(0..=50)).collect();
+
+        let counter: IndexCounter<_> = values.into_iter().collect();
+        let all = counter.most_common();
+        let all_ordered = counter.most_common_ordered();
+        for k in 0..=counter.len() {
+            assert_eq!(counter.k_most_common(k), &all[..k]);
+            assert_eq!(counter.k_most_common_ordered(k), &all_ordered[..k]);
+        }
+    }
+
+    #[test]
+    fn test_operators() {
+        let c = || IndexCounter::<_>::init("abbccc".chars());
+        let d = || IndexCounter::<_>::init("bccddd".chars());
+
+        assert_eq!(c() + d(), IndexCounter::init("abbbcccccddd".chars()));
+        assert_eq!(c() - d(), IndexCounter::init("abc".chars()));
+        assert_eq!(c() & d(), IndexCounter::init("bcc".chars()));
+        assert_eq!(c() | d(), IndexCounter::init("abbcccddd".chars()));
+        assert_eq!((c() - d()).keys().collect::<String>(), "abc");
+        assert_eq!((d() & c()).keys().collect::<String>(), "bc");
+    }
+
+    #[test]
+    fn test_index_zero() {
+        let counter = IndexCounter::<_>::init("aaa".chars());
+        assert_eq!(counter[&'a'], 3);
+        assert_eq!(counter[&'b'], 0);
+        assert_eq!(counter.get(&'b'), None);
+    }
+
+    #[test]
+    fn test_into_counter() {
+        let counter = IndexCounter::<_>::init("abracadabra".chars());
+        let hashed: Counter<_> = counter.into();
+        assert_eq!(hashed, Counter::init("abracadabra".chars()));
+    }
+}
@@ -0,0 +1,254 @@
+//! Information-theoretic statistics of the distribution described by a counter.
+//!
+//! All of these treat a counter as the probability distribution which gives each item its share
+//! of the total count. Items whose count is not positive have probability zero. Results are in
+//! nats; divide by [`LN_2`] to get bits.
+//!
+//! [`LN_2`]: https://doc.rust-lang.org/stable/std/f64/consts/constant.LN_2.html
+
+use crate::Counter;
+
+use num_traits::ToPrimitive;
+
+use std::hash::{BuildHasher, Hash};
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: ToPrimitive,
+{
+    /// Returns the positive counts as `f64`s, along with their sum.
+    pub(crate) fn positive_counts(&self) -> (impl Iterator<Item = (&T, f64)>, f64) {
+        let positive = self.map.iter().filter_map(|(item, count)| {
+            let count = count.to_f64().unwrap_or(0.0);
+            if count > 0.0 {
+                Some((item, count))
+            } else {
+                None
+            }
+        });
+        let total = positive.clone().map(|(_, count)| count).sum();
+        (positive, total)
+    }
+
+    /// Returns the [Shannon entropy] of the counts, in nats.
+    ///
+    /// An empty counter has an entropy of zero.
+    ///
+    /// [Shannon entropy]: https://en.wikipedia.org/wiki/Entropy_(information_theory)
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "aabbccdd".chars().collect::<Counter<_>>();
+    /// assert!((counter.entropy() - 4f64.ln()).abs() < 1e-12);
+    /// ```
+    pub fn entropy(&self) -> f64 {
+        let (counts, total) = self.positive_counts();
+        counts
+            .map(|(_, count)| {
+                let p = count / total;
+                -p * p.ln()
+            })
+            .sum()
+    }
+
+    /// Returns the entropy divided by its largest possible value for this many items, which is
+    /// one when all the items are equally common.
+    ///
+    /// Counters with fewer than two items have a normalized entropy of zero.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let even = "abcabc".chars().collect::<Counter<_>>();
+    /// assert!((even.normalized_entropy() - 1.0).abs() < 1e-12);
+    /// let skewed = "aaaaabc".chars().collect::<Counter<_>>();
+    /// assert!(skewed.normalized_entropy() < 0.8);
+    /// ```
+    pub fn normalized_entropy(&self) -> f64 {
+        let (counts, _) = self.positive_counts();
+        let len = counts.count();
+        if len < 2 {
+            return 0.0;
+        }
+        self.entropy() / (len as f64).ln()
+    }
+
+    /// Returns the [perplexity] of the counts, the exponential of their entropy.
+    ///
+    /// This is the number of equally common items which would have the same entropy.
+    ///
+    /// [perplexity]: https://en.wikipedia.org/wiki/Perplexity
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "abcabc".chars().collect::<Counter<_>>();
+    /// assert!((counter.perplexity() - 3.0).abs() < 1e-12);
+    /// ```
+    pub fn perplexity(&self) -> f64 {
+        self.entropy().exp()
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: ToPrimitive,
+    S: BuildHasher,
+{
+    /// Returns the [Kullback-Leibler divergence] of `other` from `self`, in nats.
+    ///
+    /// This is infinite if `other` gives zero probability to an item which `self` does not; use
+    /// a smoothed `other`, or [`js_divergence()`], if that can happen. An empty `self` has a
+    /// divergence of zero from anything.
+    ///
+    /// [Kullback-Leibler divergence]: https://en.wikipedia.org/wiki/Kullback%E2%80%93Leibler_divergence
+    /// [`js_divergence()`]: Counter::js_divergence
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let p = "aab".chars().collect::<Counter<_>>();
+    /// let q = "abbb".chars().collect::<Counter<_>>();
+    /// assert!(p.kl_divergence(&q) > 0.0);
+    /// assert_eq!(p.kl_divergence(&p), 0.0);
+    ///
+    /// let r = "aaa".chars().collect::<Counter<_>>();
+    /// assert_eq!(p.kl_divergence(&r), f64::INFINITY);
+    /// ```
+    pub fn kl_divergence(&self, other: &Counter<T, N, S>) -> f64 {
+        let (counts, total) = self.positive_counts();
+        let (_, other_total) = other.positive_counts();
+        let mut divergence = 0.0;
+        for (item, count) in counts {
+            let other_count = other.positive_count(item);
+            if other_count == 0.0 {
+                return f64::INFINITY;
+            }
+            let p = count / total;
+            divergence += p * (p / (other_count / other_total)).ln();
+        }
+        divergence
+    }
+
+    /// Returns the [Jensen-Shannon divergence] between `self` and `other`, in nats.
+    ///
+    /// Unlike the [`kl_divergence()`], this is symmetric, and always finite: it lies between
+    /// zero, for identical distributions, and ln 2, for distributions with no items in common.
+    /// Empty counters are treated as identical to everything.
+    ///
+    /// [Jensen-Shannon divergence]: https://en.wikipedia.org/wiki/Jensen%E2%80%93Shannon_divergence
+    /// [`kl_divergence()`]: Counter::kl_divergence
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let p = "aab".chars().collect::<Counter<_>>();
+    /// let q = "ccd".chars().collect::<Counter<_>>();
+    /// assert!((p.js_divergence(&q) - 2f64.ln()).abs() < 1e-12);
+    /// assert_eq!(p.js_divergence(&p), 0.0);
+    /// ```
+    pub fn js_divergence(&self, other: &Counter<T, N, S>) -> f64 {
+        let (counts, total) = self.positive_counts();
+        let (other_counts, other_total) = other.positive_counts();
+        if total == 0.0 || other_total == 0.0 {
+            return 0.0;
+        }
+
+        // Each item contributes p ln(2p / (p + q)) / 2 from `self`, and the same with p and q
+        // swapped from `other`. Items of `other` which `self` lacks contribute only the latter.
+        let half_divergence = |p: f64, q: f64| p * (2.0 * p / (p + q)).ln() / 2.0;
+        let mut divergence = 0.0;
+        for (item, count) in counts {
+            let p = count / total;
+            let q = other.positive_count(item) / other_total;
+            divergence += half_divergence(p, q);
+            if q > 0.0 {
+                divergence += half_divergence(q, p);
+            }
+        }
+        for (item, count) in other_counts {
+            if self.positive_count(item) == 0.0 {
+                divergence += half_divergence(count / other_total, 0.0);
+            }
+        }
+        divergence.max(0.0)
+    }
+
+    /// Returns the count of `item` as an `f64`, or zero if it is not positive.
+    fn positive_count(&self, item: &T) -> f64 {
+        self.map
+            .get(item)
+            .and_then(ToPrimitive::to_f64)
+            .filter(|&count| count > 0.0)
+            .unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Counter;
+
+    const EPSILON: f64 = 1e-12;
+
+    #[test]
+    fn test_uniform() {
+        let counter = Counter::<_>::init((0..10).chain(0..10));
+        assert!((counter.entropy() - 10f64.ln()).abs() < EPSILON);
+        assert!((counter.normalized_entropy() - 1.0).abs() < EPSILON);
+        assert!((counter.perplexity() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let empty = Counter::<char>::new();
+        assert_eq!(empty.entropy(), 0.0);
+        assert_eq!(empty.normalized_entropy(), 0.0);
+        assert_eq!(empty.perplexity(), 1.0);
+
+        let single = Counter::<_>::init("aaaa".chars());
+        assert_eq!(single.entropy(), 0.0);
+        assert_eq!(single.normalized_entropy(), 0.0);
+        assert_eq!(single.kl_divergence(&empty), f64::INFINITY);
+        assert_eq!(empty.kl_divergence(&single), 0.0);
+        assert_eq!(single.js_divergence(&empty), 0.0);
+    }
+
+    #[test]
+    fn test_non_positive_counts_are_ignored() {
+        let signed = vec![('a', 2), ('b', 2), ('c', 0), ('d', -5)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        assert!((signed.entropy() - 2f64.ln()).abs() < EPSILON);
+        assert!((signed.normalized_entropy() - 1.0).abs() < EPSILON);
+
+        let other = vec![('a', 1), ('b', 1), ('d', 3)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        let expected = (5.0f64 / 2.0).ln();
+        assert!((signed.kl_divergence(&other) - expected).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_float_counts() {
+        let counter = vec![("heads", 0.5), ("tails", 0.5)]
+            .into_iter()
+            .collect::<Counter<&str, f64>>();
+        assert!((counter.entropy() - 2f64.ln()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_divergences() {
+        let p = Counter::<_>::init((0..1000).map(|i| i * i % 20));
+        let q = Counter::<_>::init((0..1000).map(|i| 5 + i * 7 % 25));
+
+        assert_eq!(p.kl_divergence(&p), 0.0);
+        assert_eq!(p.kl_divergence(&q), f64::INFINITY);
+
+        let js = p.js_divergence(&q);
+        assert!((js - q.js_divergence(&p)).abs() < EPSILON);
+        assert!(js > 0.0 && js < 2f64.ln());
+
+        // The Jensen-Shannon divergence is the mean divergence from the mixture.
+        let m = p.clone() + q.clone();
+        let expected = (p.kl_divergence(&m) + q.kl_divergence(&m)) / 2.0;
+        assert!((js - expected).abs() < 1e-9);
+    }
+}
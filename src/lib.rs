@@ -20,7 +20,7 @@
 //! ```rust
 //! use counter::Counter;
 //! let char_counts = "barefoot".chars().collect::<Counter<_>>();
-//! let counts_counts = char_counts.values().collect::<Counter<_>>();
+//! let counts_counts = char_counts.counts_of_counts();
 //! ```
 //!
 //! ## Update a count
@@ -93,6 +93,35 @@
 //! [`k_most_common_ordered()`]: Counter::k_most_common_ordered
 //! [`most_common_ordered()`]: Counter::most_common_ordered
 //!
+//! [`k_least_common_ordered()`] and [`least_common_ordered()`] do the same from the other end,
+//! listing the rarest items first.
+//!
+//! ```rust
+//! # use counter::Counter;
+//! let rare = "eaddbbccc".chars().collect::<Counter<_>>().k_least_common_ordered(2);
+//! let expected = vec![('a', 1), ('e', 1)];
+//! assert!(rare == expected);
+//! ```
+//!
+//! [`k_least_common_ordered()`]: Counter::k_least_common_ordered
+//! [`least_common_ordered()`]: Counter::least_common_ordered
+//!
+//! Counts which are only [`PartialOrd`], such as `f64` weights, can be ranked with
+//! [`most_common_ordered_partial()`] and the other `_partial` methods. A [`NanPolicy`] says where
+//! NaN counts go, along with any counts which are incomparable with others.
+//!
+//! ```rust
+//! # use counter::{Counter, NanPolicy};
+//! let weights = vec![('a', 0.5), ('b', f64::NAN), ('c', 2.5)]
+//!     .into_iter()
+//!     .collect::<Counter<_, f64>>();
+//! let by_weight = weights.most_common_ordered_partial(NanPolicy::Skip);
+//! assert!(by_weight == vec![('c', 2.5), ('a', 0.5)]);
+//! ```
+//!
+//! [`PartialOrd`]: https://doc.rust-lang.org/stable/std/cmp/trait.PartialOrd.html
+//! [`most_common_ordered_partial()`]: Counter::most_common_ordered_partial
+//!
 //! ## Get the most common items using your own ordering
 //!
 //! For example, here we break ties reverse alphabetically.
@@ -174,12 +203,27 @@
 //! assert_eq!(union, expected_union)
 //! ```
 //!
-//! The in-place [`&=`] and [`|=`] operations are also supported.
+//! Their symmetric difference, with the absolute difference of the counts, comes from
+//! [`^` bitwise xor][BitXor]:
+//!
+//! ```rust
+//! # use counter::Counter;
+//! let a = "aaabb".chars().collect::<Counter<_>>();
+//! let b = "aabbbbe".chars().collect::<Counter<_>>();
+//!
+//! let difference = a ^ b;
+//! let expected_difference = "abbe".chars().collect::<Counter<_>>();
+//! assert_eq!(difference, expected_difference);
+//! ```
+//!
+//! The in-place [`&=`], [`|=`] and [`^=`] operations are also supported.
 //!
 //! [BitAnd]: https://doc.rust-lang.org/std/ops/trait.BitAnd.html
 //! [BitOr]: https://doc.rust-lang.org/std/ops/trait.BitOr.html
+//! [BitXor]: https://doc.rust-lang.org/std/ops/trait.BitXor.html
 //! [`&=`]: https://doc.rust-lang.org/std/ops/trait.BitAndAssign.html
 //! [`|=`]: https://doc.rust-lang.org/std/ops/trait.BitOrAssign.html
+//! [`^=`]: https://doc.rust-lang.org/std/ops/trait.BitXorAssign.html
 //!
 //! ## Treat it like a `HashMap`
 //!
@@ -273,35 +317,295 @@
 //! let expected: HashMap<char, i8> = [('a', 1), ('b', 2), ('c', 3)].iter().cloned().collect();
 //! assert!(counter.into_map() == expected);
 //! ```
+//!
+//! ## Keep items in sorted order
+//!
+//! [`BTreeCounter`] has the same interface as `Counter`, but is backed by a [`BTreeMap`], so it
+//! always iterates in the natural order of its keys. It can also total the counts of any range
+//! of keys with [`range_total()`]:
+//!
+//! [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
+//! [`range_total()`]: BTreeCounter::range_total
+//!
+//! ```rust
+//! # use counter::BTreeCounter;
+//! let counter = "abracadabra".chars().collect::<BTreeCounter<_>>();
+//! assert_eq!(counter.keys().collect::<String>(), "abcdr");
+//! assert_eq!(counter.range_total::<_, _, usize>('a'..'d'), 8);
+//! ```
+//!
+//! ## Keep items in insertion order
+//!
+//! With the `indexmap` feature enabled, [`IndexCounter`] offers the same interface again, backed
+//! by an [`IndexMap`]. Like Python's `Counter`, it iterates in the order in which items were
+//! first seen, and its [`most_common()`] breaks ties in that order, so results are reproducible
+//! across runs.
+//!
+//! [`IndexCounter`]: https://docs.rs/counter/latest/counter/struct.IndexCounter.html
+//! [`IndexMap`]: https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html
+//! [`most_common()`]: https://docs.rs/counter/latest/counter/struct.IndexCounter.html#method.most_common
+//!
+//! ```rust
+//! # #[cfg(feature = "indexmap")]
+//! # {
+//! # use counter::IndexCounter;
+//! let counter = "eaddbbccc".chars().collect::<IndexCounter<_>>();
+//! assert_eq!(counter.keys().collect::<String>(), "eadbc");
+//! let expected = vec![('c', 3), ('d', 2), ('b', 2), ('e', 1), ('a', 1)];
+//! assert_eq!(counter.most_common(), expected);
+//! # }
+//! ```
+//!
+//! ## Count in parallel
+//!
+//! With the `rayon` feature enabled, [`par_init()`] and [`par_update()`] count the items of a
+//! [parallel iterator][rayon]. Each thread counts its share of the items on its own, and the
+//! partial counters are then merged together. `Counter` also implements [`FromParallelIterator`]
+//! and [`ParallelExtend`].
+//!
+//! [`par_init()`]: https://docs.rs/counter/latest/counter/struct.Counter.html#method.par_init
+//! [`par_update()`]: https://docs.rs/counter/latest/counter/struct.Counter.html#method.par_update
+//! [rayon]: https://docs.rs/rayon
+//! [`FromParallelIterator`]: https://docs.rs/rayon/latest/rayon/iter/trait.FromParallelIterator.html
+//! [`ParallelExtend`]: https://docs.rs/rayon/latest/rayon/iter/trait.ParallelExtend.html
+//!
+//! ```rust
+//! # #[cfg(feature = "rayon")]
+//! # {
+//! # use counter::Counter;
+//! use rayon::prelude::*;
+//!
+//! let text = "able babble table babble rabble table able fable scrabble";
+//! let counts = text.par_split_whitespace().collect::<Counter<_>>();
+//! assert_eq!(counts, text.split_whitespace().collect::<Counter<_>>());
+//! # }
+//! ```
+//!
+//! ## Count from many threads at once
+//!
+//! Wrapping a `Counter` in a [`Mutex`] serializes every update. [`ConcurrentCounter`] instead
+//! spreads its items over independently locked shards, and can be incremented through a shared
+//! reference. Convert it into a plain `Counter` once counting is done:
+//!
+//! [`Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+//!
+//! ```rust
+//! # use counter::{ConcurrentCounter, Counter};
+//! let lines = ["able babble table babble", "rabble table able fable scrabble"];
+//! let counter = ConcurrentCounter::<_>::new();
+//! std::thread::scope(|scope| {
+//!     for line in lines {
+//!         let counter = &counter;
+//!         scope.spawn(move || counter.update(line.split_whitespace()));
+//!     }
+//! });
+//! let expected = lines.iter().flat_map(|line| line.split_whitespace()).collect::<Counter<_>>();
+//! assert_eq!(counter.into_counter(), expected);
+//! ```
+//!
+//! ## Approximate counting
+//!
+//! When there are too many distinct items to hold in memory, a [`CountMinSketch`] estimates
+//! their counts in a fixed amount of space. Its estimates are never too low, and too high by a
+//! bounded amount with high probability:
+//!
+//! ```rust
+//! # use counter::CountMinSketch;
+//! let mut sketch = CountMinSketch::with_error(0.01, 0.01);
+//! sketch.update("able babble table babble rabble table able fable scrabble".split_whitespace());
+//! assert!(sketch.estimate("babble") >= 2);
+//! let counter = sketch.to_counter(vec!["able", "babble", "cable"]);
+//! assert!(counter[&"able"] >= 2);
+//! ```
+//!
+//! To follow only the most common items of an unbounded stream, a [`HeavyHitters`] tracker keeps
+//! the counts of a fixed number of items, along with how far each count may be off:
+//!
+//! ```rust
+//! # use counter::HeavyHitters;
+//! let mut hitters = HeavyHitters::new(2);
+//! hitters.update("babble able babble table babble rabble babble".split_whitespace());
+//! assert_eq!(hitters.top(1), vec![("babble", 4)]);
+//! assert_eq!(hitters.guaranteed("babble"), 4);
+//! ```
+//!
+//! When only the number of distinct items matters, a [`HyperLogLog`] sketch estimates it without
+//! storing the items at all. A [`DistinctCounter`] keeps exact counts while there are few distinct
+//! items, and falls back to such a sketch once there are more than a given threshold:
+//!
+//! ```rust
+//! # use counter::DistinctCounter;
+//! let mut distinct = DistinctCounter::new(1000, 14);
+//! distinct.update((0..50_000).map(|i| i % 20_000));
+//! assert!(!distinct.is_exact());
+//! assert!((19_000..=21_000).contains(&distinct.len()));
+//! ```
+//!
+//! ## Sample at random
+//!
+//! With the `rand` feature enabled, [`sampler()`] draws items at random with probability
+//! proportional to their counts. It builds an alias table once, after which every draw takes
+//! constant time. To draw without replacement instead, [`sample_without_replacement()`] lowers
+//! the count of each item it draws, just like [`subtract()`]; it scans all the items on every
+//! draw, so it suits small counters or few draws.
+//!
+//! [`sampler()`]: https://docs.rs/counter/latest/counter/struct.Counter.html#method.sampler
+//! [`sample_without_replacement()`]: https://docs.rs/counter/latest/counter/struct.Counter.html#method.sample_without_replacement
+//! [`subtract()`]: https://docs.rs/counter/latest/counter/struct.Counter.html#method.subtract
+//!
+//! ```rust
+//! # #[cfg(feature = "rand")]
+//! # {
+//! # use counter::Counter;
+//! use rand::distributions::Distribution;
+//! use rand::rngs::StdRng;
+//! use rand::SeedableRng;
+//!
+//! let mut rng = StdRng::seed_from_u64(0);
+//! let mut bag = "aaaaabbbcc".chars().collect::<Counter<_>>();
+//! let sampler = bag.sampler().unwrap();
+//! let marble = sampler.sample(&mut rng);
+//! assert!("abc".contains(*marble));
+//!
+//! let hand = bag.sample_without_replacement(&mut rng, 4);
+//! assert_eq!(hand.len(), 4);
+//! assert_eq!(bag.values().sum::<usize>(), 6);
+//! # }
+//! ```
+//!
+//! ## Use a custom hasher
+//!
+//! By default a `Counter` hashes its items with the standard library's [`RandomState`], just like
+//! a [`HashMap`]. The optional third type parameter `S` swaps in any other [`BuildHasher`], for
+//! example a faster non-cryptographic hasher, or one with a fixed seed for reproducible
+//! iteration order. Construct such a counter with [`with_hasher()`] or
+//! [`with_capacity_and_hasher()`], or simply `collect()` into it when `S` implements [`Default`].
+//!
+//! [`RandomState`]: https://doc.rust-lang.org/stable/std/collections/hash_map/struct.RandomState.html
+//! [`BuildHasher`]: https://doc.rust-lang.org/stable/std/hash/trait.BuildHasher.html
+//! [`Default`]: https://doc.rust-lang.org/stable/std/default/trait.Default.html
+//! [`with_hasher()`]: Counter::with_hasher
+//! [`with_capacity_and_hasher()`]: Counter::with_capacity_and_hasher
+//!
+//! ```rust
+//! # use counter::Counter;
+//! use std::collections::hash_map::DefaultHasher;
+//! use std::hash::BuildHasherDefault;
+//!
+//! type FixedState = BuildHasherDefault<DefaultHasher>;
+//!
+//! let counter = "abbccc".chars().collect::<Counter<_, usize, FixedState>>();
+//! let other = "bccddd".chars().collect::<Counter<_, usize, FixedState>>();
+//! let sum = counter + other;
+//! assert_eq!(sum[&'c'], 5);
+//! ```
+//!
+//! ## Serialize and deserialize
+//!
+//! With the `serde` feature enabled, `Counter<T, N>` implements [`Serialize`] and
+//! [`Deserialize`], using the same representation as the underlying [`HashMap`]. When
+//! deserializing, non-positive counts are dropped, so a loaded counter holds only the positive
+//! counts that [`update`] would have produced.
+//!
+//! [`Serialize`]: https://docs.rs/serde/latest/serde/trait.Serialize.html
+//! [`Deserialize`]: https://docs.rs/serde/latest/serde/trait.Deserialize.html
+//! [`update`]: Counter::update
+//!
+//! ```rust
+//! # #[cfg(feature = "serde")]
+//! # {
+//! # use counter::Counter;
+//! let counter = "abbccc".chars().collect::<Counter<_>>();
+//! let json = serde_json::to_string(&counter).unwrap();
+//! let loaded: Counter<char> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(counter, loaded);
+//! # }
+//! ```
 
-use num_traits::{One, Zero};
+mod btree;
+mod by_count;
+mod concurrent;
+mod count_min;
+mod distribution;
+mod elements;
+mod frequencies;
+mod heavy_hitters;
+mod hyperloglog;
+#[cfg(feature = "indexmap")]
+mod indexed;
+mod information;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod rank;
+mod ref_ops;
+#[cfg(feature = "rand")]
+mod sampling;
+#[cfg(feature = "serde")]
+mod serialize;
+mod similarity;
+
+pub use btree::BTreeCounter;
+pub use by_count::IterByCount;
+pub use concurrent::ConcurrentCounter;
+pub use count_min::CountMinSketch;
+pub use distribution::{Distribution, Smoothing};
+pub use elements::Elements;
+pub use heavy_hitters::HeavyHitters;
+pub use hyperloglog::{DistinctCounter, HyperLogLog};
+#[cfg(feature = "indexmap")]
+pub use indexed::IndexCounter;
+pub use rank::{CountRank, NanPolicy};
+#[cfg(feature = "rand")]
+pub use sampling::Sampler;
+
+use num_traits::{One, Signed, Zero};
 
 use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
 use std::collections::{BinaryHeap, HashMap};
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::iter;
 use std::ops::{
-    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, Deref, DerefMut, Index, IndexMut,
-    Sub, SubAssign,
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Deref,
+    DerefMut, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
 };
 
-type CounterMap<T, N> = HashMap<T, N>;
+type CounterMap<T, N, S> = HashMap<T, N, S>;
 
-#[derive(Clone, PartialEq, Eq, Debug)]
-pub struct Counter<T: Hash + Eq, N = usize> {
-    map: CounterMap<T, N>,
+#[derive(Clone, Debug)]
+pub struct Counter<T: Hash + Eq, N = usize, S = RandomState> {
+    map: CounterMap<T, N, S>,
     // necessary for `Index::index` since we cannot declare generic `static` variables.
     zero: N,
 }
 
-impl<T, N> Counter<T, N>
+impl<T, N, S> PartialEq for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<T, N, S> Eq for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Eq,
+    S: BuildHasher,
+{
+}
+
+impl<T, N, S> Counter<T, N, S>
 where
     T: Hash + Eq,
 {
     /// Consumes this counter and returns a [`HashMap`] mapping the items to the counts.
     ///
     /// [`HashMap`]: https://doc.rust-lang.org/stable/std/collections/struct.HashMap.html
-    pub fn into_map(self) -> HashMap<T, N> {
+    pub fn into_map(self) -> HashMap<T, N, S> {
         self.map
     }
 
@@ -320,9 +624,9 @@ where
     /// assert_eq!(counter.total::<usize>(), 11);
     /// assert_eq!(counter.len(), 5);
     /// ```
-    pub fn total<'a, S>(&'a self) -> S
+    pub fn total<'a, R>(&'a self) -> R
     where
-        S: iter::Sum<&'a N>,
+        R: iter::Sum<&'a N>,
     {
         self.map.values().sum()
     }
@@ -342,6 +646,46 @@ where
     }
 }
 
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Zero,
+    S: BuildHasher,
+{
+    /// Create a new, empty `Counter` which will use the given hash builder to hash its items.
+    ///
+    /// This is useful to plug in a faster hasher than the default [`RandomState`], or a hasher
+    /// with a fixed seed for reproducible iteration order.
+    ///
+    /// [`RandomState`]: https://doc.rust-lang.org/stable/std/collections/hash_map/struct.RandomState.html
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// let mut counter: Counter<char, usize, _> =
+    ///     Counter::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+    /// counter.update("abbccc".chars());
+    /// assert_eq!(counter[&'c'], 3);
+    /// ```
+    pub fn with_hasher(hash_builder: S) -> Counter<T, N, S> {
+        Counter {
+            map: HashMap::with_hasher(hash_builder),
+            zero: N::zero(),
+        }
+    }
+
+    /// Create a new, empty `Counter` with space for at least `capacity` distinct items, which
+    /// will use the given hash builder to hash its items.
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Counter<T, N, S> {
+        Counter {
+            map: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+            zero: N::zero(),
+        }
+    }
+}
+
 impl<T, N> Counter<T, N>
 where
     T: Hash + Eq,
@@ -356,7 +700,14 @@ where
         counter.update(iterable);
         counter
     }
+}
 
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero + One,
+    S: BuildHasher,
+{
     /// Add the counts of the elements from the given iterable to this counter.
     pub fn update<I>(&mut self, iterable: I)
     where
@@ -369,14 +720,17 @@ where
     }
 }
 
-impl<T, N> Counter<T, N>
+impl<T, N, S> Counter<T, N, S>
 where
     T: Hash + Eq,
     N: PartialOrd + SubAssign + Zero + One,
+    S: BuildHasher,
 {
     /// Remove the counts of the elements from the given iterable to this counter.
     ///
-    /// Non-positive counts are automatically removed.
+    /// Non-positive counts are automatically removed. Use [`subtract_signed()`] to keep them.
+    ///
+    /// [`subtract_signed()`]: Counter::subtract_signed
     ///
     /// ```rust
     /// # use counter::Counter;
@@ -405,7 +759,209 @@ where
     }
 }
 
-impl<T, N> Counter<T, N>
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Signed + SubAssign,
+    S: BuildHasher,
+{
+    /// Remove the counts of the elements from the given iterable to this counter, like
+    /// Python's `Counter.subtract`.
+    ///
+    /// Unlike [`subtract()`], counts are allowed to drop to zero or below, and such entries are
+    /// kept.
+    ///
+    /// [`subtract()`]: Counter::subtract
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut counter = "abbccc".chars().collect::<Counter<_, i32>>();
+    /// counter.subtract_signed("abbaa".chars());
+    /// let expect = [('a', -2), ('b', 0), ('c', 3)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(counter.into_map(), expect);
+    /// ```
+    pub fn subtract_signed<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iterable {
+            let entry = self.map.entry(item).or_insert_with(N::zero);
+            *entry -= N::one();
+        }
+    }
+
+    /// Remove the counts of `(item, count)` tuples from the given iterable to this counter,
+    /// keeping entries whose counts drop to zero or below.
+    ///
+    /// Since a `Counter` iterates over such tuples, this also subtracts another counter.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut ledger = Counter::<_, i64>::new();
+    /// ledger.subtract_signed_counts(vec![("rent", 1200), ("food", 300)]);
+    /// ledger.subtract_signed_counts(vec![("food", -100)]);
+    /// let expect = [("rent", -1200), ("food", -200)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(ledger.into_map(), expect);
+    /// ```
+    pub fn subtract_signed_counts<I>(&mut self, iterable: I)
+    where
+        I: IntoIterator<Item = (T, N)>,
+    {
+        for (item, item_count) in iterable {
+            let entry = self.map.entry(item).or_insert_with(N::zero);
+            *entry -= item_count;
+        }
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + Zero,
+{
+    /// Consume this counter, keeping only the items with a positive count.
+    ///
+    /// This is Python's unary `+c`.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut counter = "abb".chars().collect::<Counter<_, i32>>();
+    /// counter.subtract_signed("aac".chars());
+    /// let expect = [('b', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(counter.into_positive().into_map(), expect);
+    /// ```
+    pub fn into_positive(mut self) -> Counter<T, N, S> {
+        self.map.retain(|_, count| *count > N::zero());
+        self
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + SubAssign + Zero,
+    S: BuildHasher,
+{
+    /// Consume this counter and `other`, returning what each one has in excess of the other.
+    ///
+    /// `(left, right) = c.absolute_difference(d);` -> `left[x] == c[x] - d[x]` and
+    /// `right[x] == d[x] - c[x]`, each keeping only items with a value greater than
+    /// [`N::zero()`]. Together they hold the same counts as `c ^ d`, split by which side had the
+    /// surplus.
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let warehouse = "aaabbc".chars().collect::<Counter<_>>();
+    /// let ledger = "abbbd".chars().collect::<Counter<_>>();
+    ///
+    /// let (unrecorded, missing) = warehouse.absolute_difference(ledger);
+    ///
+    /// let expect = [('a', 2), ('c', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(unrecorded.into_map(), expect);
+    /// let expect = [('b', 1), ('d', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(missing.into_map(), expect);
+    /// ```
+    pub fn absolute_difference(
+        mut self,
+        mut other: Counter<T, N, S>,
+    ) -> (Counter<T, N, S>, Counter<T, N, S>) {
+        self.map.retain(|key, lhs_count| {
+            if let Some(rhs_count) = other.map.get_mut(key) {
+                if *lhs_count >= *rhs_count {
+                    *lhs_count -= std::mem::replace(rhs_count, N::zero());
+                } else {
+                    *rhs_count -= std::mem::replace(lhs_count, N::zero());
+                }
+            }
+            *lhs_count > N::zero()
+        });
+        other.map.retain(|_, count| *count > N::zero());
+        (self, other)
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd,
+{
+    /// Remove every item whose count is less than `min`.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// counter.retain_min_count(2);
+    /// let expect = [('a', 5), ('b', 2), ('r', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(counter.into_map(), expect);
+    /// ```
+    pub fn retain_min_count(&mut self, min: N) {
+        self.map.retain(|_, count| *count >= min);
+    }
+
+    /// Remove every item whose count is greater than `max`.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// counter.retain_max_count(2);
+    /// let expect = [('b', 2), ('r', 2), ('c', 1), ('d', 1)]
+    ///     .iter()
+    ///     .cloned()
+    ///     .collect::<HashMap<_, _>>();
+    /// assert_eq!(counter.into_map(), expect);
+    /// ```
+    pub fn retain_max_count(&mut self, max: N) {
+        self.map.retain(|_, count| *count <= max);
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + Zero,
+    S: BuildHasher + Clone,
+{
+    /// Remove every item whose count is less than `threshold`, and return them in a new counter.
+    ///
+    /// This is [`retain_min_count()`], keeping hold of what was pruned.
+    ///
+    /// [`retain_min_count()`]: Counter::retain_min_count
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// let rare = counter.drain_below(2);
+    /// let expect = [('a', 5), ('b', 2), ('r', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(counter.into_map(), expect);
+    /// let expect = [('c', 1), ('d', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(rare.into_map(), expect);
+    /// ```
+    pub fn drain_below(&mut self, threshold: N) -> Counter<T, N, S> {
+        let hasher = self.map.hasher().clone();
+        let mut below = Counter::with_hasher(hasher.clone());
+        let kept = HashMap::with_capacity_and_hasher(self.map.len(), hasher);
+        for (key, count) in std::mem::replace(&mut self.map, kept) {
+            if count < threshold {
+                below.map.insert(key, count);
+            } else {
+                self.map.insert(key, count);
+            }
+        }
+        below
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
 where
     T: Hash + Eq + Clone,
     N: Clone + Ord,
@@ -455,24 +1011,126 @@ where
         });
         items
     }
+
+    /// Create a vector of `(elem, frequency)` pairs, sorted least to most common.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let lc = "pappaopolo".chars().collect::<Counter<_>>().least_common();
+    /// let expected = vec![('l', 1), ('a', 2), ('o', 3), ('p', 4)];
+    /// assert_eq!(lc, expected);
+    /// ```
+    ///
+    /// Note that the ordering of duplicates is unstable.
+    pub fn least_common(&self) -> Vec<(T, N)> {
+        let mut items = self
+            .map
+            .iter()
+            .map(|(key, count)| (key.clone(), count.clone()))
+            .collect::<Vec<_>>();
+        items.sort_unstable_by(|(_, a_count), (_, b_count)| a_count.cmp(b_count));
+        items
+    }
 }
 
-impl<T, N> Counter<T, N>
+impl<T, N, S> Counter<T, N, S>
 where
-    T: Hash + Eq + Clone + Ord,
-    N: Clone + Ord,
+    T: Hash + Eq,
+    N: Ord,
 {
-    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common.
+    /// Create a vector of `(&elem, &frequency)` pairs, sorted most to least common.
     ///
-    /// In the event that two keys have an equal frequency, use the natural ordering of the keys
-    /// to further sort the results.
+    /// This is [`most_common()`] without cloning the keys or counts.
     ///
-    /// # Examples
+    /// [`most_common()`]: Counter::most_common
     ///
     /// ```rust
     /// # use counter::Counter;
-    /// let mc = "abracadabra".chars().collect::<Counter<_>>().most_common_ordered();
-    /// let expect = vec![('a', 5), ('b', 2), ('r', 2), ('c', 1), ('d', 1)];
+    /// let counter = "pappaopolo".chars().collect::<Counter<_>>();
+    /// let mc = counter.most_common_refs();
+    /// let expected = vec![(&'p', &4), (&'o', &3), (&'a', &2), (&'l', &1)];
+    /// assert_eq!(mc, expected);
+    /// ```
+    ///
+    /// Note that the ordering of duplicates is unstable.
+    pub fn most_common_refs(&self) -> Vec<(&T, &N)> {
+        let mut items = self.map.iter().collect::<Vec<_>>();
+        items.sort_unstable_by(|(_, a_count), (_, b_count)| b_count.cmp(a_count));
+        items
+    }
+
+    /// Returns references to the `k` most common items in decreasing order of their counts.
+    ///
+    /// If `k` is greater than the length of the counter then the returned vector will have length
+    /// equal to that of the counter, not `k`.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "pappaopolo".chars().collect::<Counter<_>>();
+    /// let top2 = counter.k_most_common_refs(2);
+    /// assert_eq!(top2, vec![(&'p', &4), (&'o', &3)]);
+    /// ```
+    ///
+    /// Note that the ordering of duplicates is unstable, and so is which of the items tied with
+    /// the `k`th are included.
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n* + *k* \* log *k*), where *n* is the number of items in the counter: the top `k`
+    /// items are selected in linear time, and only they are sorted.
+    pub fn k_most_common_refs(&self, k: usize) -> Vec<(&T, &N)> {
+        let mut items = self.map.iter().collect::<Vec<_>>();
+        let compare = |(_, a_count): &(&T, &N), (_, b_count): &(&T, &N)| b_count.cmp(a_count);
+        if k < items.len() {
+            items.select_nth_unstable_by(k, compare);
+            items.truncate(k);
+        }
+        items.sort_unstable_by(compare);
+        items
+    }
+
+    /// Consume this counter, returning a vector of `(elem, frequency)` pairs sorted most to least
+    /// common.
+    ///
+    /// This is [`most_common()`] for when the counter is no longer needed: the keys are moved
+    /// rather than cloned, so they need not be [`Clone`].
+    ///
+    /// [`most_common()`]: Counter::most_common
+    /// [`Clone`]: https://doc.rust-lang.org/stable/std/clone/trait.Clone.html
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = vec!["one".to_string(), "two".to_string(), "two".to_string()]
+    ///     .into_iter()
+    ///     .collect::<Counter<_>>();
+    /// let mc = counter.into_most_common();
+    /// assert_eq!(mc, vec![("two".to_string(), 2), ("one".to_string(), 1)]);
+    /// ```
+    ///
+    /// Note that the ordering of duplicates is unstable.
+    pub fn into_most_common(self) -> Vec<(T, N)> {
+        let mut items = self.map.into_iter().collect::<Vec<_>>();
+        items.sort_unstable_by(|(_, a_count), (_, b_count)| b_count.cmp(a_count));
+        items
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Clone + Ord,
+    N: Clone + Ord,
+{
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common.
+    ///
+    /// In the event that two keys have an equal frequency, use the natural ordering of the keys
+    /// to further sort the results.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let mc = "abracadabra".chars().collect::<Counter<_>>().most_common_ordered();
+    /// let expect = vec![('a', 5), ('b', 2), ('r', 2), ('c', 1), ('d', 1)];
     /// assert_eq!(mc, expect);
     /// ```
     ///
@@ -538,38 +1196,160 @@ where
         // comparing counts.  This will be an improvement in the typical case where `N: Copy`.
         // Defer cloning the keys until we have selected the top `k` items so that we clone only
         // `k` keys instead of all of them.
-        let mut items = self.map.iter().map(|(t, n)| (Reverse(n.clone()), t));
-
-        // Step 1. Make a heap out of the first `k` items; this makes O(k) comparisons.
-        let mut heap: BinaryHeap<_> = items.by_ref().take(k).collect();
-
-        // Step 2. Successively compare each of the remaining `n - k` items to the top of the heap,
-        // replacing the root (and subsequently sifting down) whenever the item is less than the
-        // root.  This takes at most n - k + k * (1 + log2(k)) * (H(n) - H(k)) comparisons, where
-        // H(i) is the ith [harmonic number](https://en.wikipedia.org/wiki/Harmonic_number).  For
-        // fixed `k`, this scales as *n* + *O*(log(*n*)).
-        items.for_each(|item| {
-            // If `items` is nonempty at this point then we know the heap contains `k > 0`
-            // elements.
-            let mut root = heap.peek_mut().expect("the heap is empty");
-            if *root > item {
-                *root = item;
-            }
-        });
+        let items = self.map.iter().map(|(t, n)| (Reverse(n.clone()), t));
+        let heap = k_smallest(items, k);
 
-        // Step 3. Sort the items in the heap with the second phases of heapsort.  The number of
+        // Sort the items in the heap with the second phase of heapsort.  The number of
         // comparisons is 2 * k * log2(k) + O(k).
         heap.into_sorted_vec()
             .into_iter()
             .map(|(Reverse(n), t)| (t.clone(), n))
             .collect()
     }
+
+    /// Create a vector of `(elem, frequency)` pairs, sorted least to most common.
+    ///
+    /// In the event that two keys have an equal frequency, use the natural ordering of the keys
+    /// to further sort the results.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let lc = "abracadabra".chars().collect::<Counter<_>>().least_common_ordered();
+    /// let expect = vec![('c', 1), ('d', 1), ('b', 2), ('r', 2), ('a', 5)];
+    /// assert_eq!(lc, expect);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n* \* log *n*), where *n* is the number of items in the counter.  If all you want is
+    /// the bottom *k* items and *k* < *n* then it can be more efficient to use
+    /// [`k_least_common_ordered`].
+    ///
+    /// [`k_least_common_ordered`]: Counter::k_least_common_ordered
+    pub fn least_common_ordered(&self) -> Vec<(T, N)> {
+        let mut items = self
+            .map
+            .iter()
+            .map(|(key, count)| (key.clone(), count.clone()))
+            .collect::<Vec<_>>();
+        items.sort_unstable_by(|(a_item, a_count), (b_item, b_count)| {
+            a_count.cmp(b_count).then_with(|| a_item.cmp(b_item))
+        });
+        items
+    }
+
+    /// Returns the `k` least common items in increasing order of their counts.
+    ///
+    /// The returned vector is the same as would be obtained by calling `least_common_ordered`
+    /// and then truncating the result to length `k`.  Items with the same count are sorted in
+    /// increasing order of their keys, and if `k` is greater than the length of the counter then
+    /// the returned vector will have length equal to that of the counter, not `k`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter: Counter<_> = "abracadabra".chars().collect();
+    /// let bottom3 = counter.k_least_common_ordered(3);
+    /// assert_eq!(bottom3, vec![('c', 1), ('d', 1), ('b', 2)]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The same as that of [`k_most_common_ordered`], which uses the same algorithm.
+    ///
+    /// [`k_most_common_ordered`]: Counter::k_most_common_ordered
+    pub fn k_least_common_ordered(&self, k: usize) -> Vec<(T, N)> {
+        if k == 0 {
+            return vec![];
+        }
+
+        if k >= self.map.len() {
+            return self.least_common_ordered();
+        }
+
+        let items = self.map.iter().map(|(t, n)| (n.clone(), t));
+        k_smallest(items, k)
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(n, t)| (t.clone(), n))
+            .collect()
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Ord,
+    N: Ord,
+    S: BuildHasher,
+{
+    /// Remove every item except the `k` most common.
+    ///
+    /// The items kept are the same as those returned by [`k_most_common_ordered`]: items with
+    /// the same count are ranked in *increasing* order of their keys. If `k` is at least the
+    /// length of the counter then nothing is removed.
+    ///
+    /// [`k_most_common_ordered`]: Counter::k_most_common_ordered
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// counter.keep_top_k(2);
+    /// let expect = [('a', 5), ('b', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(counter.into_map(), expect);
+    /// ```
+    pub fn keep_top_k(&mut self, k: usize) {
+        use std::cmp::Reverse;
+
+        if k >= self.map.len() {
+            return;
+        }
+        let items = self.map.drain().map(|(t, n)| (Reverse(n), t));
+        let heap = k_smallest(items, k);
+        self.map
+            .extend(heap.into_iter().map(|(Reverse(n), t)| (t, n)));
+    }
+}
+
+/// Returns a max-heap of the `k` smallest of `items`.
+///
+/// See [`Counter::k_most_common_ordered`] for the time complexity.
+pub(crate) fn k_smallest<I>(mut items: I, k: usize) -> BinaryHeap<I::Item>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    if k == 0 {
+        return BinaryHeap::new();
+    }
+
+    // Step 1. Make a heap out of the first `k` items; this makes O(k) comparisons.
+    let mut heap: BinaryHeap<_> = items.by_ref().take(k).collect();
+
+    // Step 2. Successively compare each of the remaining `n - k` items to the top of the heap,
+    // replacing the root (and subsequently sifting down) whenever the item is less than the
+    // root.  This takes at most n - k + k * (1 + log2(k)) * (H(n) - H(k)) comparisons, where
+    // H(i) is the ith [harmonic number](https://en.wikipedia.org/wiki/Harmonic_number).  For
+    // fixed `k`, this scales as *n* + *O*(log(*n*)).
+    items.for_each(|item| {
+        // If `items` is nonempty at this point then we know the heap contains `k > 0`
+        // elements.
+        let mut root = heap.peek_mut().expect("the heap is empty");
+        if *root > item {
+            *root = item;
+        }
+    });
+    heap
 }
 
-impl<T, N> Default for Counter<T, N>
+impl<T, N, S> Default for Counter<T, N, S>
 where
     T: Hash + Eq,
     N: Default,
+    S: Default,
 {
     fn default() -> Self {
         Self {
@@ -579,10 +1359,11 @@ where
     }
 }
 
-impl<T, N> AddAssign for Counter<T, N>
+impl<T, N, S> AddAssign for Counter<T, N, S>
 where
     T: Hash + Eq,
     N: Zero + AddAssign,
+    S: BuildHasher,
 {
     /// Add another counter to this counter.
     ///
@@ -607,12 +1388,13 @@ where
     }
 }
 
-impl<T, N> Add for Counter<T, N>
+impl<T, N, S> Add for Counter<T, N, S>
 where
     T: Clone + Hash + Eq,
     N: AddAssign + Zero,
+    S: BuildHasher,
 {
-    type Output = Counter<T, N>;
+    type Output = Counter<T, N, S>;
 
     /// Add two counters together.
     ///
@@ -629,16 +1411,17 @@ where
     /// let expect = [('a', 4), ('b', 3)].iter().cloned().collect::<HashMap<_, _>>();
     /// assert_eq!(e.into_map(), expect);
     /// ```
-    fn add(mut self, rhs: Counter<T, N>) -> Self::Output {
+    fn add(mut self, rhs: Counter<T, N, S>) -> Self::Output {
         self += rhs;
         self
     }
 }
 
-impl<T, N> SubAssign for Counter<T, N>
+impl<T, N, S> SubAssign for Counter<T, N, S>
 where
     T: Hash + Eq,
     N: PartialOrd + PartialEq + SubAssign + Zero,
+    S: BuildHasher,
 {
     /// Subtract (keeping only positive values).
     ///
@@ -679,12 +1462,13 @@ where
     }
 }
 
-impl<T, N> Sub for Counter<T, N>
+impl<T, N, S> Sub for Counter<T, N, S>
 where
     T: Hash + Eq,
     N: PartialOrd + PartialEq + SubAssign + Zero,
+    S: BuildHasher,
 {
-    type Output = Counter<T, N>;
+    type Output = Counter<T, N, S>;
 
     /// Subtract (keeping only positive values).
     ///
@@ -705,231 +1489,517 @@ where
     /// let expect = [('a', 2)].iter().cloned().collect::<HashMap<_, _>>();
     /// assert_eq!(e.into_map(), expect);
     /// ```
-    fn sub(mut self, rhs: Counter<T, N>) -> Self::Output {
+    fn sub(mut self, rhs: Counter<T, N, S>) -> Self::Output {
         self -= rhs;
         self
     }
 }
 
-impl<T, N> Counter<T, N>
+impl<T, N, S> Counter<T, N, S>
 where
     T: Hash + Eq,
     N: PartialOrd + Zero,
+    S: BuildHasher,
 {
     /// Test whether this counter is a superset of another counter.
     /// This is true if for all elements in this counter and the other,
     /// the count in this counter is greater than or equal to the count in the other.
     ///
-    /// `c.is_superset(&d);` -> `c.iter().all(|(x, n)| n >= d[x]) && d.iter().all(|(x, n)| c[x] >= n)`
+    /// `c.is_superset(&d);` -> `c.iter().all(|(x, n)| n >= d[x]) && d.iter().all(|(x, n)| c[x] >= n)`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let c = "aaabbc".chars().collect::<Counter<_>>();
+    /// let mut d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// assert!(c.is_superset(&d));
+    /// d[&'e'] = 1;
+    /// assert!(!c.is_superset(&d));
+    /// ```
+    pub fn is_superset(&self, other: &Self) -> bool {
+        // need to test keys from both counters, because if N is signed, counts in `self`
+        // could be < 0 for elements missing in `other`. For the unsigned case, only elements
+        // from `other` would need to be tested.
+        self.keys()
+            .chain(other.keys())
+            .all(|key| self[key] >= other[key])
+    }
+
+    /// Test whether this counter is a subset of another counter.
+    /// This is true if for all elements in this counter and the other,
+    /// the count in this counter is less than or equal to the count in the other.
+    ///
+    /// `c.is_subset(&d);` -> `c.iter().all(|(x, n)| n <= d[x]) && d.iter().all(|(x, n)| c[x] <= n)`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut c = "abb".chars().collect::<Counter<_>>();
+    /// let mut d = "aaabbc".chars().collect::<Counter<_>>();
+    ///
+    /// assert!(c.is_subset(&d));
+    /// c[&'e'] = 1;
+    /// assert!(!c.is_subset(&d));
+    /// ```
+    pub fn is_subset(&self, other: &Self) -> bool {
+        // need to test keys from both counters, because if N is signed, counts in `other`
+        // could be < 0 for elements missing in `self`. For the unsigned case, only elements
+        // from `self` would need to be tested.
+        self.keys()
+            .chain(other.keys())
+            .all(|key| self[key] <= other[key])
+    }
+}
+
+impl<T, N, S> BitAnd for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the intersection of `self` and `rhs` as a new `Counter`.
+    ///
+    /// `out = c & d;` -> `out[x] == min(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// let e = c & d;
+    ///
+    /// let expect = [('a', 1), ('b', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(e.into_map(), expect);
+    /// ```
+    fn bitand(mut self, mut rhs: Counter<T, N, S>) -> Self::Output {
+        // Retain the entries of `self` in place rather than building a new counter, so that the
+        // result keeps the hash builder of `self` without requiring `S: Clone` or `S: Default`.
+        self.map.retain(|key, lhs_count| match rhs.map.remove(key) {
+            Some(rhs_count) => {
+                if rhs_count < *lhs_count {
+                    *lhs_count = rhs_count;
+                }
+                true
+            }
+            None => false,
+        });
+        self
+    }
+}
+
+impl<T, N, S> BitAndAssign for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero,
+    S: BuildHasher,
+{
+    /// Updates `self` with the intersection of `self` and `rhs`
+    ///
+    /// `c &= d;` -> `c[x] == min(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// c &= d;
+    ///
+    /// let expect = [('a', 1), ('b', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(c.into_map(), expect);
+    /// ```
+    fn bitand_assign(&mut self, mut rhs: Counter<T, N, S>) {
+        for (key, rhs_count) in rhs.drain() {
+            if rhs_count < self[&key] {
+                self.map.insert(key, rhs_count);
+            }
+        }
+    }
+}
+
+impl<T, N, S> BitOr for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the union of `self` and `rhs` as a new `Counter`.
+    ///
+    /// `out = c | d;` -> `out[x] == max(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// let e = c | d;
+    ///
+    /// let expect = [('a', 3), ('b', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(e.into_map(), expect);
+    /// ```
+    fn bitor(mut self, rhs: Counter<T, N, S>) -> Self::Output {
+        for (key, rhs_value) in rhs.map {
+            let entry = self.map.entry(key).or_insert_with(N::zero);
+            // We want to update the value of the now occupied entry in `self` with the maximum of
+            // its current value and `rhs_value`.  If that max is `rhs_value`, we can just update
+            // the value of the entry.  If the max is the current value, we do nothing.  Note that
+            // `Ord::max()` returns the second argument (here `rhs_value`) if its two arguments are
+            // equal, justifying the use of the weak inequality below instead of a strict
+            // inequality.
+            //
+            // Doing it this way with an inequality instead of actually using `std::cmp::max()`
+            // lets us avoid trying (and failing) to move the non-copy value out of the entry in
+            // order to pass it as an argument to `std::cmp::max()`, while still holding a mutable
+            // reference to the value slot in the entry.
+            //
+            // And while using the inequality seemingly only requires the bound `N: PartialOrd`, we
+            // nevertheless prefer to require `Ord` as though we were using `std::cmp::max()`
+            // because the semantics of `BitOr` for `Counter` really do not make sense if there are
+            // possibly non-comparable values of type `N`.
+            if rhs_value >= *entry {
+                *entry = rhs_value;
+            }
+        }
+        self
+    }
+}
+
+impl<T, N, S> BitOrAssign for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero,
+    S: BuildHasher,
+{
+    /// Updates `self` with the union of `self` and `rhs`
+    ///
+    /// `c |= d;` -> `c[x] == max(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// c |= d;
+    ///
+    /// let expect = [('a', 3), ('b', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(c.into_map(), expect);
+    /// ```
+    fn bitor_assign(&mut self, mut rhs: Counter<T, N, S>) {
+        for (key, rhs_count) in rhs.drain() {
+            if rhs_count > self[&key] {
+                self.map.insert(key, rhs_count);
+            }
+        }
+    }
+}
+
+impl<T, N, S> BitXor for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + SubAssign + Zero,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the symmetric difference of `self` and `rhs` as a new `Counter`.
+    ///
+    /// `out = c ^ d;` -> `out[x] == max(c[x], d[x]) - min(c[x], d[x])`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abbc".chars().collect::<Counter<_>>();
+    ///
+    /// let e = c ^ d;
+    ///
+    /// let expect = [('a', 2), ('b', 1), ('c', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(e.into_map(), expect);
+    /// ```
+    fn bitxor(mut self, rhs: Counter<T, N, S>) -> Self::Output {
+        self ^= rhs;
+        self
+    }
+}
+
+impl<T, N, S> BitXorAssign for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + SubAssign + Zero,
+    S: BuildHasher,
+{
+    /// Updates `self` with the symmetric difference of `self` and `rhs`
+    ///
+    /// `c ^= d;` -> `c[x] == max(c[x], d[x]) - min(c[x], d[x])`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abbc".chars().collect::<Counter<_>>();
+    ///
+    /// c ^= d;
+    ///
+    /// let expect = [('a', 2), ('b', 1), ('c', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(c.into_map(), expect);
+    /// ```
+    fn bitxor_assign(&mut self, rhs: Counter<T, N, S>) {
+        for (key, mut rhs_count) in rhs.map {
+            match self.map.get_mut(&key) {
+                Some(lhs_count) => {
+                    if *lhs_count >= rhs_count {
+                        *lhs_count -= rhs_count;
+                    } else {
+                        rhs_count -= std::mem::replace(lhs_count, N::zero());
+                        *lhs_count = rhs_count;
+                    }
+                }
+                None => {
+                    self.map.insert(key, rhs_count);
+                }
+            }
+        }
+        self.map.retain(|_, count| *count > N::zero());
+    }
+}
+
+impl<T, N, S> Neg for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + Neg<Output = N> + Zero,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Negate, keeping only the items which had a negative count.
+    ///
+    /// This is Python's unary `-c`: `out = -c;` -> `out[x] == -c[x]` for all `x` with
+    /// `c[x] < 0`.
     ///
     /// ```rust
     /// # use counter::Counter;
     /// # use std::collections::HashMap;
-    /// let c = "aaabbc".chars().collect::<Counter<_>>();
-    /// let mut d = "abb".chars().collect::<Counter<_>>();
+    /// let mut c = "abb".chars().collect::<Counter<_, i32>>();
+    /// c.subtract_signed("aaac".chars());
     ///
-    /// assert!(c.is_superset(&d));
-    /// d[&'e'] = 1;
-    /// assert!(!c.is_superset(&d));
+    /// let e = -c;
+    ///
+    /// let expect = [('a', 2), ('c', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(e.into_map(), expect);
     /// ```
-    pub fn is_superset(&self, other: &Self) -> bool {
-        // need to test keys from both counters, because if N is signed, counts in `self`
-        // could be < 0 for elements missing in `other`. For the unsigned case, only elements
-        // from `other` would need to be tested.
-        self.keys()
-            .chain(other.keys())
-            .all(|key| self[key] >= other[key])
+    fn neg(mut self) -> Self::Output {
+        self.map.retain(|_, count| *count < N::zero());
+        for count in self.map.values_mut() {
+            *count = -std::mem::replace(count, N::zero());
+        }
+        self
     }
+}
 
-    /// Test whether this counter is a subset of another counter.
-    /// This is true if for all elements in this counter and the other,
-    /// the count in this counter is less than or equal to the count in the other.
+impl<T, N, S> MulAssign<N> for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Clone + MulAssign + Zero,
+{
+    /// Multiply every count by a scalar.
     ///
-    /// `c.is_subset(&d);` -> `c.iter().all(|(x, n)| n <= d[x]) && d.iter().all(|(x, n)| c[x] <= n)`
+    /// `c *= n;` -> `c[x] *= n` for all `x`, removing items whose count becomes
+    /// [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
     ///
     /// ```rust
     /// # use counter::Counter;
     /// # use std::collections::HashMap;
-    /// let mut c = "abb".chars().collect::<Counter<_>>();
-    /// let mut d = "aaabbc".chars().collect::<Counter<_>>();
+    /// let mut c = "aaab".chars().collect::<Counter<_>>();
     ///
-    /// assert!(c.is_subset(&d));
-    /// c[&'e'] = 1;
-    /// assert!(!c.is_subset(&d));
+    /// c *= 2;
+    ///
+    /// let expect = [('a', 6), ('b', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(c.into_map(), expect);
     /// ```
-    pub fn is_subset(&self, other: &Self) -> bool {
-        // need to test keys from both counters, because if N is signed, counts in `other`
-        // could be < 0 for elements missing in `self`. For the unsigned case, only elements
-        // from `self` would need to be tested.
-        self.keys()
-            .chain(other.keys())
-            .all(|key| self[key] <= other[key])
+    fn mul_assign(&mut self, rhs: N) {
+        self.scale_by(|count| {
+            let mut count = count.clone();
+            count *= rhs.clone();
+            count
+        });
     }
 }
 
-impl<T, N> BitAnd for Counter<T, N>
+impl<T, N, S> Mul<N> for Counter<T, N, S>
 where
     T: Hash + Eq,
-    N: Ord + Zero,
+    N: Clone + MulAssign + Zero,
 {
-    type Output = Counter<T, N>;
+    type Output = Counter<T, N, S>;
 
-    /// Returns the intersection of `self` and `rhs` as a new `Counter`.
+    /// Multiply every count by a scalar.
     ///
-    /// `out = c & d;` -> `out[x] == min(c[x], d[x])`
+    /// `out = c * n;` -> `out[x] == c[x] * n` for all `x`, keeping only items whose count is not
+    /// [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
     ///
     /// ```rust
     /// # use counter::Counter;
     /// # use std::collections::HashMap;
-    /// let c = "aaab".chars().collect::<Counter<_>>();
-    /// let d = "abb".chars().collect::<Counter<_>>();
+    /// let local = "aab".chars().collect::<Counter<_>>();
+    /// let remote = "bc".chars().collect::<Counter<_>>();
     ///
-    /// let e = c & d;
+    /// let e = local * 3 + remote;
     ///
-    /// let expect = [('a', 1), ('b', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// let expect = [('a', 6), ('b', 4), ('c', 1)].iter().cloned().collect::<HashMap<_, _>>();
     /// assert_eq!(e.into_map(), expect);
     /// ```
-    fn bitand(self, mut rhs: Counter<T, N>) -> Self::Output {
-        use std::cmp::min;
-
-        let mut counter = Counter::new();
-        for (key, lhs_count) in self.map {
-            if let Some(rhs_count) = rhs.remove(&key) {
-                let count = min(lhs_count, rhs_count);
-                counter.map.insert(key, count);
-            }
-        }
-        counter
+    fn mul(mut self, rhs: N) -> Self::Output {
+        self *= rhs;
+        self
     }
 }
 
-impl<T, N> BitAndAssign for Counter<T, N>
+impl<T, N, S> DivAssign<N> for Counter<T, N, S>
 where
     T: Hash + Eq,
-    N: Ord + Zero,
+    N: Clone + DivAssign + Zero,
 {
-    /// Updates `self` with the intersection of `self` and `rhs`
+    /// Divide every count by a scalar.
     ///
-    /// `c &= d;` -> `c[x] == min(c[x], d[x])`
+    /// `c /= n;` -> `c[x] /= n` for all `x`, removing items whose count becomes
+    /// [`N::zero()`]. For integer counts the division rounds towards zero, so small counts may
+    /// drop out.
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
     ///
     /// ```rust
     /// # use counter::Counter;
     /// # use std::collections::HashMap;
-    /// let mut c = "aaab".chars().collect::<Counter<_>>();
-    /// let d = "abb".chars().collect::<Counter<_>>();
+    /// let mut c = "aaaabbc".chars().collect::<Counter<_>>();
     ///
-    /// c &= d;
+    /// c /= 2;
     ///
-    /// let expect = [('a', 1), ('b', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// let expect = [('a', 2), ('b', 1)].iter().cloned().collect::<HashMap<_, _>>();
     /// assert_eq!(c.into_map(), expect);
     /// ```
-    fn bitand_assign(&mut self, mut rhs: Counter<T, N>) {
-        for (key, rhs_count) in rhs.drain() {
-            if rhs_count < self[&key] {
-                self.map.insert(key, rhs_count);
-            }
-        }
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero, the counts are integers and the counter is not empty.
+    fn div_assign(&mut self, rhs: N) {
+        self.scale_by(|count| {
+            let mut count = count.clone();
+            count /= rhs.clone();
+            count
+        });
     }
 }
 
-impl<T, N> BitOr for Counter<T, N>
+impl<T, N, S> Div<N> for Counter<T, N, S>
 where
     T: Hash + Eq,
-    N: Ord + Zero,
+    N: Clone + DivAssign + Zero,
 {
-    type Output = Counter<T, N>;
+    type Output = Counter<T, N, S>;
 
-    /// Returns the union of `self` and `rhs` as a new `Counter`.
+    /// Divide every count by a scalar.
     ///
-    /// `out = c | d;` -> `out[x] == max(c[x], d[x])`
+    /// `out = c / n;` -> `out[x] == c[x] / n` for all `x`, keeping only items whose count is
+    /// not [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
     ///
     /// ```rust
     /// # use counter::Counter;
     /// # use std::collections::HashMap;
-    /// let c = "aaab".chars().collect::<Counter<_>>();
-    /// let d = "abb".chars().collect::<Counter<_>>();
+    /// let c = "aaab".chars().collect::<Counter<_, f64>>();
     ///
-    /// let e = c | d;
+    /// let e = c / 4.0;
     ///
-    /// let expect = [('a', 3), ('b', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// let expect = [('a', 0.75), ('b', 0.25)].iter().cloned().collect::<HashMap<_, _>>();
     /// assert_eq!(e.into_map(), expect);
     /// ```
-    fn bitor(mut self, rhs: Counter<T, N>) -> Self::Output {
-        for (key, rhs_value) in rhs.map {
-            let entry = self.map.entry(key).or_insert_with(N::zero);
-            // We want to update the value of the now occupied entry in `self` with the maximum of
-            // its current value and `rhs_value`.  If that max is `rhs_value`, we can just update
-            // the value of the entry.  If the max is the current value, we do nothing.  Note that
-            // `Ord::max()` returns the second argument (here `rhs_value`) if its two arguments are
-            // equal, justifying the use of the weak inequality below instead of a strict
-            // inequality.
-            //
-            // Doing it this way with an inequality instead of actually using `std::cmp::max()`
-            // lets us avoid trying (and failing) to move the non-copy value out of the entry in
-            // order to pass it as an argument to `std::cmp::max()`, while still holding a mutable
-            // reference to the value slot in the entry.
-            //
-            // And while using the inequality seemingly only requires the bound `N: PartialOrd`, we
-            // nevertheless prefer to require `Ord` as though we were using `std::cmp::max()`
-            // because the semantics of `BitOr` for `Counter` really do not make sense if there are
-            // possibly non-comparable values of type `N`.
-            if rhs_value >= *entry {
-                *entry = rhs_value;
-            }
-        }
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero, the counts are integers and the counter is not empty.
+    fn div(mut self, rhs: N) -> Self::Output {
+        self /= rhs;
         self
     }
 }
 
-impl<T, N> BitOrAssign for Counter<T, N>
+impl<T, N, S> Counter<T, N, S>
 where
     T: Hash + Eq,
-    N: Ord + Zero,
+    N: Zero,
 {
-    /// Updates `self` with the union of `self` and `rhs`
+    /// Replace every count with the result of `f`, removing items whose new count is
+    /// [`N::zero()`].
     ///
-    /// `c |= d;` -> `c[x] == max(c[x], d[x])`
+    /// This is the general form of `*=` and `/=`, for reweighting which a single scalar cannot
+    /// express.
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
     ///
     /// ```rust
     /// # use counter::Counter;
     /// # use std::collections::HashMap;
-    /// let mut c = "aaab".chars().collect::<Counter<_>>();
-    /// let d = "abb".chars().collect::<Counter<_>>();
-    ///
-    /// c |= d;
-    ///
-    /// let expect = [('a', 3), ('b', 2)].iter().cloned().collect::<HashMap<_, _>>();
-    /// assert_eq!(c.into_map(), expect);
+    /// let mut counter = "aaaabbc".chars().collect::<Counter<_>>();
+    /// counter.scale_by(|&count| count.saturating_sub(1));
+    /// let expect = [('a', 3), ('b', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(counter.into_map(), expect);
     /// ```
-    fn bitor_assign(&mut self, mut rhs: Counter<T, N>) {
-        for (key, rhs_count) in rhs.drain() {
-            if rhs_count > self[&key] {
-                self.map.insert(key, rhs_count);
-            }
-        }
+    pub fn scale_by<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&N) -> N,
+    {
+        self.map.retain(|_, count| {
+            *count = f(count);
+            !count.is_zero()
+        });
     }
 }
 
-impl<T, N> Deref for Counter<T, N>
+impl<T, N, S> Deref for Counter<T, N, S>
 where
     T: Hash + Eq,
 {
-    type Target = CounterMap<T, N>;
-    fn deref(&self) -> &CounterMap<T, N> {
+    type Target = CounterMap<T, N, S>;
+    fn deref(&self) -> &CounterMap<T, N, S> {
         &self.map
     }
 }
 
-impl<T, N> DerefMut for Counter<T, N>
+impl<T, N, S> DerefMut for Counter<T, N, S>
 where
     T: Hash + Eq,
 {
-    fn deref_mut(&mut self) -> &mut CounterMap<T, N> {
+    fn deref_mut(&mut self) -> &mut CounterMap<T, N, S> {
         &mut self.map
     }
 }
 
-impl<'a, T, N> IntoIterator for &'a Counter<T, N>
+impl<'a, T, N, S> IntoIterator for &'a Counter<T, N, S>
 where
     T: Hash + Eq,
 {
@@ -941,7 +2011,7 @@ where
     }
 }
 
-impl<T, N> IntoIterator for Counter<T, N>
+impl<T, N, S> IntoIterator for Counter<T, N, S>
 where
     T: Hash + Eq,
 {
@@ -967,13 +2037,12 @@ where
     ///     }
     /// }
     /// ```
-
     fn into_iter(self) -> Self::IntoIter {
         self.map.into_iter()
     }
 }
 
-impl<'a, T, N> IntoIterator for &'a mut Counter<T, N>
+impl<'a, T, N, S> IntoIterator for &'a mut Counter<T, N, S>
 where
     T: Hash + Eq,
 {
@@ -1003,11 +2072,12 @@ where
     }
 }
 
-impl<T, Q, N> Index<&'_ Q> for Counter<T, N>
+impl<T, Q, N, S> Index<&'_ Q> for Counter<T, N, S>
 where
     T: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq,
     N: Zero,
+    S: BuildHasher,
 {
     type Output = N;
 
@@ -1045,11 +2115,12 @@ where
     }
 }
 
-impl<T, Q, N> IndexMut<&'_ Q> for Counter<T, N>
+impl<T, Q, N, S> IndexMut<&'_ Q> for Counter<T, N, S>
 where
     T: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq + ToOwned<Owned = T>,
     N: Zero,
+    S: BuildHasher,
 {
     /// Index in mutable contexts.
     ///
@@ -1087,11 +2158,12 @@ where
     }
 }
 
-impl<I, T, N> AddAssign<I> for Counter<T, N>
+impl<I, T, N, S> AddAssign<I> for Counter<T, N, S>
 where
     I: IntoIterator<Item = T>,
     T: Hash + Eq,
     N: AddAssign + Zero + One,
+    S: BuildHasher,
 {
     /// Directly add the counts of the elements of `I` to `self`.
     ///
@@ -1110,11 +2182,12 @@ where
     }
 }
 
-impl<I, T, N> Add<I> for Counter<T, N>
+impl<I, T, N, S> Add<I> for Counter<T, N, S>
 where
     I: IntoIterator<Item = T>,
     T: Hash + Eq,
     N: AddAssign + Zero + One,
+    S: BuildHasher,
 {
     type Output = Self;
     /// Consume `self` producing a `Counter` like `self` updated with the counts of
@@ -1136,11 +2209,12 @@ where
     }
 }
 
-impl<I, T, N> SubAssign<I> for Counter<T, N>
+impl<I, T, N, S> SubAssign<I> for Counter<T, N, S>
 where
     I: IntoIterator<Item = T>,
     T: Hash + Eq,
     N: PartialOrd + SubAssign + Zero + One,
+    S: BuildHasher,
 {
     /// Directly subtract the counts of the elements of `I` from `self`,
     /// keeping only items with a value greater than [`N::zero()`].
@@ -1162,11 +2236,12 @@ where
     }
 }
 
-impl<I, T, N> Sub<I> for Counter<T, N>
+impl<I, T, N, S> Sub<I> for Counter<T, N, S>
 where
     I: IntoIterator<Item = T>,
     T: Hash + Eq,
     N: PartialOrd + SubAssign + Zero + One,
+    S: BuildHasher,
 {
     type Output = Self;
     /// Consume `self` producing a `Counter` like `self` with the counts of the
@@ -1187,10 +2262,11 @@ where
     }
 }
 
-impl<T, N> iter::FromIterator<T> for Counter<T, N>
+impl<T, N, S> iter::FromIterator<T> for Counter<T, N, S>
 where
     T: Hash + Eq,
     N: AddAssign + Zero + One,
+    S: BuildHasher + Default,
 {
     /// Produce a `Counter` from an iterator of items. This is called automatically
     /// by [`Iterator::collect()`].
@@ -1207,14 +2283,17 @@ where
     /// ```
     ///
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        Counter::<T, N>::init(iter)
+        let mut counter = Counter::with_hasher(S::default());
+        counter.update(iter);
+        counter
     }
 }
 
-impl<T, N> iter::FromIterator<(T, N)> for Counter<T, N>
+impl<T, N, S> iter::FromIterator<(T, N)> for Counter<T, N, S>
 where
     T: Hash + Eq,
     N: AddAssign + Zero,
+    S: BuildHasher + Default,
 {
     /// Creates a counter from `(item, count)` tuples.
     ///
@@ -1229,7 +2308,7 @@ where
     /// assert_eq!(counter.into_map(), expect);
     /// ```
     fn from_iter<I: IntoIterator<Item = (T, N)>>(iter: I) -> Self {
-        let mut cnt = Counter::new();
+        let mut cnt = Counter::with_hasher(S::default());
         for (item, item_count) in iter {
             let entry = cnt.map.entry(item).or_insert_with(N::zero);
             *entry += item_count;
@@ -1238,10 +2317,11 @@ where
     }
 }
 
-impl<T, N> Extend<T> for Counter<T, N>
+impl<T, N, S> Extend<T> for Counter<T, N, S>
 where
     T: Hash + Eq,
     N: AddAssign + Zero + One,
+    S: BuildHasher,
 {
     /// Extend a `Counter` with an iterator of items.
     ///
@@ -1258,10 +2338,11 @@ where
     }
 }
 
-impl<T, N> Extend<(T, N)> for Counter<T, N>
+impl<T, N, S> Extend<(T, N)> for Counter<T, N, S>
 where
     T: Hash + Eq,
     N: AddAssign + Zero,
+    S: BuildHasher,
 {
     /// Extend a counter with `(item, count)` tuples.
     ///
@@ -1283,10 +2364,11 @@ where
     }
 }
 
-impl<'a, T: 'a, N: 'a> Extend<(&'a T, &'a N)> for Counter<T, N>
+impl<'a, T: 'a, N: 'a, S> Extend<(&'a T, &'a N)> for Counter<T, N, S>
 where
     T: Hash + Eq + Clone,
     N: AddAssign + Zero + Clone,
+    S: BuildHasher,
 {
     /// Extend a counter with `(item, count)` tuples.
     ///
@@ -1309,11 +2391,26 @@ where
     }
 }
 
+/// Hashes `key` with a hasher from `hash_builder`.
+///
+/// This is `BuildHasher::hash_one`, which is not available before Rust 1.71.
+#[allow(clippy::manual_hash_one)]
+pub(crate) fn hash_one<S, Q>(hash_builder: &S, key: &Q) -> u64
+where
+    S: BuildHasher,
+    Q: Hash + ?Sized,
+{
+    let mut hasher = hash_builder.build_hasher();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use maplit::hashmap;
-    use rand::Rng;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use std::collections::HashMap;
 
     #[test]
@@ -1401,6 +2498,35 @@ mod tests {
         assert!(counter.map == expected);
     }
 
+    #[test]
+    fn test_subtract_signed() {
+        let mut counter = Counter::<_, i32>::init("abbccc".chars());
+        counter.subtract_signed("aabbbd".chars());
+        let expected = hashmap! {
+            'a' => -1,
+            'b' => -1,
+            'c' => 3,
+            'd' => -1,
+        };
+        assert!(counter.map == expected);
+
+        counter.subtract_signed_counts(Counter::<_, i32>::init("cccd".chars()));
+        let expected = hashmap! {
+            'a' => -1,
+            'b' => -1,
+            'c' => 0,
+            'd' => -2,
+        };
+        assert!(counter.map == expected);
+    }
+
+    #[test]
+    fn test_neg_and_into_positive() {
+        let counter: Counter<_, i32> = vec![('a', 2), ('b', 0), ('c', -3)].into_iter().collect();
+        assert!((-counter.clone()).map == hashmap! { 'c' => 3 });
+        assert!(counter.into_positive().map == hashmap! { 'a' => 2 });
+    }
+
     #[test]
     fn test_sub_update_iterable() {
         let mut counter = Counter::init("abbccc".chars());
@@ -1521,6 +2647,116 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_most_common_refs() {
+        #[derive(Debug, Hash, PartialEq, Eq)]
+        struct Unclonable(char);
+
+        let counter = "eaddbbccc".chars().map(Unclonable).collect::<Counter<_>>();
+        let counts = |items: Vec<(&Unclonable, &usize)>| {
+            items
+                .into_iter()
+                .map(|(_, &count)| count)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(counts(counter.most_common_refs()), vec![3, 2, 2, 1, 1]);
+        assert_eq!(counter.most_common_refs()[0], (&Unclonable('c'), &3));
+        for k in 0..=counter.len() + 1 {
+            let expected = [3, 2, 2, 1, 1].iter().take(k).cloned().collect::<Vec<_>>();
+            assert_eq!(counts(counter.k_most_common_refs(k)), expected);
+        }
+
+        let owned = counter.into_most_common();
+        let counts = owned.iter().map(|(_, count)| *count).collect::<Vec<_>>();
+        assert_eq!(counts, vec![3, 2, 2, 1, 1]);
+        assert_eq!(owned[0].0, Unclonable('c'));
+    }
+
+    #[test]
+    fn test_least_common() {
+        let counter = Counter::init("eaddbbccc".chars());
+        let counts = counter
+            .least_common()
+            .into_iter()
+            .map(|(_, count)| count)
+            .collect::<Vec<usize>>();
+        assert_eq!(counts, vec![1, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_least_common_ordered() {
+        let counter = Counter::<_>::init("eaddbbccc".chars());
+        let by_common = counter.least_common_ordered();
+        let expected = vec![('a', 1), ('e', 1), ('b', 2), ('d', 2), ('c', 3)];
+        assert!(by_common == expected);
+
+        let mut reversed = counter.most_common_ordered();
+        reversed.sort_by(|(a_item, a_count), (b_item, b_count)| {
+            a_count.cmp(b_count).then_with(|| a_item.cmp(b_item))
+        });
+        assert_eq!(by_common, reversed);
+    }
+
+    #[test]
+    fn test_k_least_common_ordered() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for container_size in [5, 10, 25, 100, 256] {
+            let values = (0..container_size).map(|_| rng.gen_range(0..=container_size / 2));
+            let counter: Counter<_> = values.collect();
+            let all = counter.least_common_ordered();
+            for k in 0..=counter.len() {
+                let bottomk = counter.k_least_common_ordered(k);
+                assert_eq!(&bottomk, &all[..k]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_keep_top_k() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let counter: Counter<_> = (0..500).map(|_| rng.gen_range(0..50)).collect();
+        let all = counter.most_common_ordered();
+        for k in 0..=counter.len() + 1 {
+            let mut pruned = counter.clone();
+            pruned.keep_top_k(k);
+            assert_eq!(pruned.most_common_ordered(), &all[..k.min(all.len())]);
+        }
+    }
+
+    #[test]
+    fn test_retain_count_bounds() {
+        let counter = Counter::<_>::init("abbcccdddd".chars());
+
+        let mut common = counter.clone();
+        common.retain_min_count(3);
+        assert!(common == Counter::init("cccdddd".chars()));
+
+        let mut rare = counter.clone();
+        rare.retain_max_count(2);
+        assert!(rare == Counter::init("abb".chars()));
+
+        let mut band = counter;
+        band.retain_min_count(2);
+        band.retain_max_count(3);
+        assert!(band == Counter::init("bbccc".chars()));
+    }
+
+    #[test]
+    fn test_drain_below() {
+        let counter = Counter::<_>::init("abbcccdddd".chars());
+
+        let mut kept = counter.clone();
+        let drained = kept.drain_below(3);
+        assert!(kept == Counter::init("cccdddd".chars()));
+        assert!(drained == Counter::init("abb".chars()));
+        assert!(kept.clone() + drained == counter);
+
+        assert!(kept.drain_below(0).is_empty());
+        assert!(kept.drain_below(10) == Counter::init("cccdddd".chars()));
+        assert!(kept.is_empty());
+    }
+
     #[test]
     fn test_total() {
         let counter = Counter::init("".chars());
@@ -1572,6 +2808,93 @@ mod tests {
         assert!(out == expected);
     }
 
+    #[test]
+    fn test_symmetric_difference() {
+        let d = Counter::<_>::init("abbccc".chars());
+        let e = Counter::<_>::init("bccddd".chars());
+
+        let out = d.clone() ^ e.clone();
+        let expected = Counter::init("abcddd".chars());
+        assert!(out == expected);
+        assert!(e.clone() ^ d.clone() == expected);
+
+        let mut inplace = d.clone();
+        inplace ^= e.clone();
+        assert!(inplace == expected);
+
+        assert!((d.clone() ^ d).is_empty());
+    }
+
+    #[test]
+    fn test_absolute_difference() {
+        let d = Counter::<_>::init("abbccc".chars());
+        let e = Counter::<_>::init("bccddd".chars());
+
+        let (left, right) = d.clone().absolute_difference(e.clone());
+        assert!(left == Counter::init("abc".chars()));
+        assert!(right == Counter::init("ddd".chars()));
+        assert!(left.clone() + right.clone() == d.clone() ^ e.clone());
+        assert!(left == d.clone() - e.clone());
+        assert!(right == e - d);
+    }
+
+    #[test]
+    fn test_scalar_multiplication() {
+        let d = Counter::<_>::init("abbccc".chars());
+
+        let tripled = d.clone() * 3;
+        assert!(tripled == Counter::init("abbccc".repeat(3).chars()));
+        let mut zeroed = d.clone();
+        zeroed.scale_by(|_| 0);
+        assert!(zeroed.is_empty());
+
+        let mut signed = vec![('a', 2), ('b', -1)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        signed *= -2;
+        let expected = hashmap! {
+            'a' => -4,
+            'b' => 2,
+        };
+        assert_eq!(signed.into_map(), expected);
+    }
+
+    #[test]
+    fn test_scalar_division() {
+        let d = Counter::<_>::init("abbccc".chars());
+
+        let halved = d.clone() / 2;
+        assert!(halved == Counter::init("bc".chars()));
+        assert!((d / 4).is_empty());
+
+        let mut weights = vec![('a', 1.0), ('b', 3.0)]
+            .into_iter()
+            .collect::<Counter<char, f64>>();
+        weights /= 2.0;
+        let expected = hashmap! {
+            'a' => 0.5,
+            'b' => 1.5,
+        };
+        assert_eq!(weights.into_map(), expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_scalar_division_by_zero() {
+        let _ = Counter::<_>::init("abc".chars()) / 0;
+    }
+
+    #[test]
+    fn test_scale_by() {
+        let mut counter = Counter::<_, i32>::init("abbccc".chars());
+        counter.scale_by(|&count| count * count - 1);
+        let expected = hashmap! {
+            'b' => 3,
+            'c' => 8,
+        };
+        assert_eq!(counter.into_map(), expected);
+    }
+
     #[test]
     fn test_delete_key_from_backing_map() {
         let mut counter = Counter::<_>::init("aa-bb-cc".chars());
@@ -1705,6 +3028,50 @@ mod tests {
         assert!(counter.map == expected);
     }
 
+    #[test]
+    fn test_custom_hasher() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::BuildHasherDefault;
+
+        type FixedState = BuildHasherDefault<DefaultHasher>;
+
+        let mut counter: Counter<char, usize, FixedState> =
+            Counter::with_capacity_and_hasher(4, FixedState::default());
+        counter.update("abbccc".chars());
+        counter += "ad".chars();
+        let expected = hashmap! {
+            'a' => 2,
+            'b' => 2,
+            'c' => 3,
+            'd' => 1,
+        };
+        assert!(counter.map.iter().all(|(k, v)| expected[k] == *v));
+        assert_eq!(counter.len(), expected.len());
+
+        let other = "bccddd".chars().collect::<Counter<_, usize, FixedState>>();
+        assert_eq!(
+            counter.clone() + other.clone(),
+            "aabbbcccccdddd".chars().collect()
+        );
+        assert_eq!(counter.clone() - other.clone(), "aabc".chars().collect());
+        assert_eq!(counter.clone() & other.clone(), "bccd".chars().collect());
+        assert_eq!(counter | other, "aabbcccddd".chars().collect());
+    }
+
+    #[test]
+    fn test_custom_hasher_iteration_is_reproducible() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::BuildHasherDefault;
+
+        type FixedState = BuildHasherDefault<DefaultHasher>;
+
+        let keys = || (0..1000u32).map(|i| i * 37 % 101);
+        let a = keys().collect::<Counter<_, usize, FixedState>>();
+        let b = keys().collect::<Counter<_, usize, FixedState>>();
+        assert_eq!(a.len(), 101);
+        assert!(a.iter().eq(b.iter()));
+    }
+
     #[test]
     fn test_superset_non_usize_count() {
         let mut a: Counter<_, i8> = "abbcccc".chars().collect();
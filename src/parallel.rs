@@ -0,0 +1,257 @@
+//! Parallel counting with [`rayon`], enabled with the `rayon` feature.
+//!
+//! Each worker thread counts its share of the items into a counter of its own, and the partial
+//! counters are then merged with [`AddAssign`].
+//!
+//! [`rayon`]: https://docs.rs/rayon
+//! [`AddAssign`]: https://doc.rust-lang.org/stable/std/ops/trait.AddAssign.html
+
+use crate::Counter;
+
+use num_traits::{One, Zero};
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+use std::hash::{BuildHasher, Hash};
+use std::ops::AddAssign;
+
+impl<T, N> Counter<T, N>
+where
+    T: Hash + Eq + Send,
+    N: AddAssign + Zero + One + Send,
+{
+    /// Create a new `Counter` initialized with the given parallel iterable.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// use rayon::prelude::*;
+    ///
+    /// let words = vec!["able", "babble", "table", "babble", "able", "able"];
+    /// let counter: Counter<_> = Counter::par_init(words.par_iter());
+    /// assert_eq!(counter[&"able"], 3);
+    /// assert_eq!(counter[&"babble"], 2);
+    /// ```
+    pub fn par_init<I>(iterable: I) -> Counter<T, N>
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        let mut counter = Counter::new();
+        counter.par_update(iterable);
+        counter
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Send,
+    N: AddAssign + Zero + One + Send,
+    S: BuildHasher + Clone + Send + Sync,
+{
+    /// Add the counts of the elements from the given parallel iterable to this counter.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut counter = "abbccc".chars().collect::<Counter<_>>();
+    /// counter.par_update("aeeeee".par_chars());
+    /// assert_eq!(counter, "aabbccceeeee".chars().collect());
+    /// ```
+    pub fn par_update<I>(&mut self, iterable: I)
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        let hash_builder = self.map.hasher().clone();
+        let counts = iterable
+            .into_par_iter()
+            .fold(
+                || Counter::with_hasher(hash_builder.clone()),
+                |mut counter, item| {
+                    let entry = counter.map.entry(item).or_insert_with(N::zero);
+                    *entry += N::one();
+                    counter
+                },
+            )
+            .reduce_with(merge);
+        if let Some(counts) = counts {
+            *self += counts;
+        }
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Send,
+    N: AddAssign + Zero + Send,
+    S: BuildHasher + Clone + Send + Sync,
+{
+    /// Add `(item, count)` tuples from the given parallel iterable to this counter.
+    fn par_update_counts<I>(&mut self, iterable: I)
+    where
+        I: IntoParallelIterator<Item = (T, N)>,
+    {
+        let hash_builder = self.map.hasher().clone();
+        let counts = iterable
+            .into_par_iter()
+            .fold(
+                || Counter::with_hasher(hash_builder.clone()),
+                |mut counter, (item, item_count)| {
+                    let entry = counter.map.entry(item).or_insert_with(N::zero);
+                    *entry += item_count;
+                    counter
+                },
+            )
+            .reduce_with(merge);
+        if let Some(counts) = counts {
+            *self += counts;
+        }
+    }
+}
+
+/// Merge two partial counters, folding the smaller one into the larger.
+fn merge<T, N, S>(mut a: Counter<T, N, S>, mut b: Counter<T, N, S>) -> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: AddAssign + Zero,
+    S: BuildHasher,
+{
+    if a.len() < b.len() {
+        std::mem::swap(&mut a, &mut b);
+    }
+    a += b;
+    a
+}
+
+impl<T, N, S> FromParallelIterator<T> for Counter<T, N, S>
+where
+    T: Hash + Eq + Send,
+    N: AddAssign + Zero + One + Send,
+    S: BuildHasher + Default + Clone + Send + Sync,
+{
+    /// Produce a `Counter` from a parallel iterator of items. This is called automatically by
+    /// [`ParallelIterator::collect()`].
+    ///
+    /// [`ParallelIterator::collect()`]:
+    /// https://docs.rs/rayon/latest/rayon/iter/trait.ParallelIterator.html#method.collect
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// use rayon::prelude::*;
+    ///
+    /// let counter = (0..1000).into_par_iter().map(|i| i % 3).collect::<Counter<_>>();
+    /// assert_eq!(counter[&0], 334);
+    /// assert_eq!(counter[&1], 333);
+    /// assert_eq!(counter[&2], 333);
+    /// ```
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        let mut counter = Counter::with_hasher(S::default());
+        counter.par_update(par_iter);
+        counter
+    }
+}
+
+impl<T, N, S> FromParallelIterator<(T, N)> for Counter<T, N, S>
+where
+    T: Hash + Eq + Send,
+    N: AddAssign + Zero + Send,
+    S: BuildHasher + Default + Clone + Send + Sync,
+{
+    /// Creates a counter from a parallel iterator of `(item, count)` tuples.
+    ///
+    /// The counts of duplicate items are summed.
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = (T, N)>,
+    {
+        let mut counter = Counter::with_hasher(S::default());
+        counter.par_update_counts(par_iter);
+        counter
+    }
+}
+
+impl<T, N, S> ParallelExtend<T> for Counter<T, N, S>
+where
+    T: Hash + Eq + Send,
+    N: AddAssign + Zero + One + Send,
+    S: BuildHasher + Clone + Send + Sync,
+{
+    /// Extend a `Counter` with a parallel iterator of items.
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        self.par_update(par_iter);
+    }
+}
+
+impl<T, N, S> ParallelExtend<(T, N)> for Counter<T, N, S>
+where
+    T: Hash + Eq + Send,
+    N: AddAssign + Zero + Send,
+    S: BuildHasher + Clone + Send + Sync,
+{
+    /// Extend a counter with a parallel iterator of `(item, count)` tuples.
+    ///
+    /// The counts of duplicate items are summed.
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (T, N)>,
+    {
+        self.par_update_counts(par_iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Counter;
+    use rayon::prelude::*;
+
+    #[test]
+    fn test_par_init_matches_init() {
+        let values: Vec<u32> = (0..10_000).map(|i| i * i % 97).collect();
+
+        let counter: Counter<_> = Counter::par_init(values.par_iter().cloned());
+        assert_eq!(counter, Counter::init(values.iter().cloned()));
+    }
+
+    #[test]
+    fn test_par_init_empty() {
+        let counter: Counter<u32> = Counter::par_init(Vec::new());
+        assert!(counter.is_empty());
+    }
+
+    #[test]
+    fn test_par_update() {
+        let mut counter = Counter::<_>::init("abbccc".chars());
+        counter.par_update("aeeeee".par_chars());
+        assert_eq!(counter, Counter::init("aabbccceeeee".chars()));
+    }
+
+    #[test]
+    fn test_par_collect_and_extend() {
+        // Enough items that rayon splits the work and merges the partial counters.
+        let mut counter = (0..10_000)
+            .into_par_iter()
+            .map(|i| i % 7)
+            .collect::<Counter<_>>();
+        assert_eq!(counter.len(), 7);
+        assert_eq!(counter[&0], 1429);
+        assert_eq!(counter[&6], 1428);
+
+        counter.par_extend((0..7).into_par_iter());
+        assert_eq!(counter[&0], 1430);
+        assert_eq!(counter.total::<usize>(), 10_007);
+    }
+
+    #[test]
+    fn test_par_collect_and_extend_tuples() {
+        let items = vec![('a', 1), ('b', 2), ('c', 3), ('a', 4)];
+        let mut counter = items.par_iter().cloned().collect::<Counter<char>>();
+        assert_eq!(counter, items.iter().cloned().collect());
+
+        counter.par_extend(items.into_par_iter());
+        assert_eq!(counter[&'a'], 10);
+    }
+}
@@ -0,0 +1,483 @@
+//! Rankings of the items of a counter which treat equal counts as ties, or whose counts are only
+//! partially ordered.
+
+use crate::Counter;
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::hash::{BuildHasher, Hash};
+
+/// The rank of an item among the items of a counter, from most to least common.
+///
+/// Both ranks start from one for the most common items, and items with equal counts share a
+/// rank; they differ in what comes after a tie. For counts of 5, 3, 3 and 1 the dense ranks are
+/// 1, 2, 2 and 3, and the competition ranks are 1, 2, 2 and 4.
+///
+/// This `struct` is created by [`Counter::count_rank`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CountRank {
+    /// One more than the number of distinct counts greater than this item's count.
+    pub dense: usize,
+    /// One more than the number of items with a greater count than this item.
+    pub competition: usize,
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Clone + Ord,
+    N: Clone + Ord,
+{
+    /// Returns the `k` most common items in decreasing order of their counts, along with every
+    /// other item which ties with the last of them.
+    ///
+    /// Where [`k_most_common_ordered`] cuts a group of equal counts at length `k`, this returns
+    /// every item whose count is at least the `k`th greatest, so the result may be longer than
+    /// `k`. Items with the same count are sorted in increasing order of their keys.
+    ///
+    /// [`k_most_common_ordered`]: Counter::k_most_common_ordered
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// assert_eq!(counter.k_most_common_ordered(2), vec![('a', 5), ('b', 2)]);
+    /// assert_eq!(
+    ///     counter.k_most_common_with_ties(2),
+    ///     vec![('a', 5), ('b', 2), ('r', 2)],
+    /// );
+    /// ```
+    pub fn k_most_common_with_ties(&self, k: usize) -> Vec<(T, N)> {
+        let mut top = self.k_most_common_ordered(k);
+        let threshold = match top.last() {
+            Some((_, count)) if top.len() < self.map.len() => count.clone(),
+            _ => return top,
+        };
+        let ties = self
+            .map
+            .iter()
+            .filter(|&(_, count)| *count == threshold)
+            .map(|(item, count)| (item.clone(), count.clone()));
+        top.retain(|(_, count)| *count > threshold);
+        let first_tie = top.len();
+        top.extend(ties);
+        top[first_tie..].sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        top
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord,
+    S: BuildHasher,
+{
+    /// Returns the rank of `key` among the items of the counter by count, or `None` if it has not
+    /// been counted.
+    ///
+    /// See [`CountRank`] for how ties are ranked.
+    ///
+    /// ```rust
+    /// # use counter::{Counter, CountRank};
+    /// let counter = "abracadabra".chars().collect::<Counter<_>>();
+    /// assert_eq!(counter.count_rank(&'a'), Some(CountRank { dense: 1, competition: 1 }));
+    /// assert_eq!(counter.count_rank(&'r'), Some(CountRank { dense: 2, competition: 2 }));
+    /// assert_eq!(counter.count_rank(&'c'), Some(CountRank { dense: 3, competition: 4 }));
+    /// assert_eq!(counter.count_rank(&'z'), None);
+    /// ```
+    pub fn count_rank<Q>(&self, key: &Q) -> Option<CountRank>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let count = self.map.get(key)?;
+        let mut greater = self
+            .map
+            .values()
+            .filter(|&other| other > count)
+            .collect::<Vec<_>>();
+        let competition = greater.len() + 1;
+        greater.sort_unstable();
+        greater.dedup();
+        Some(CountRank {
+            dense: greater.len() + 1,
+            competition,
+        })
+    }
+}
+
+/// Where the `_partial` ranking methods place counts which cannot be ranked, such as NaN.
+///
+/// A count cannot be ranked if it is not comparable with itself, like NaN, or with the count of
+/// some other item, which can happen for types whose order is only partial. All such counts tie
+/// with each other, and the policy calls them all NaN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NanPolicy {
+    /// Rank NaN counts as smaller than every other count.
+    Smallest,
+    /// Rank NaN counts as greater than every other count.
+    Largest,
+    /// Leave items with NaN counts out of the ranking.
+    Skip,
+}
+
+impl NanPolicy {
+    /// Sorts `items` from most to least common, breaking ties with `tiebreaker` and placing the
+    /// items whose counts cannot be ranked according to this policy.
+    fn rank<'a, T, N, F>(self, items: Vec<(&'a T, &'a N)>, mut tiebreaker: F) -> Vec<(&'a T, &'a N)>
+    where
+        N: PartialOrd,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let (mut ranked, mut unranked) = split_unranked(items);
+        // Every pair of ranked counts is comparable, so this is a total order.
+        ranked.sort_unstable_by(|&(a_item, a_count), &(b_item, b_count)| {
+            b_count
+                .partial_cmp(a_count)
+                .expect("ranked counts are comparable")
+                .then_with(|| tiebreaker(a_item, b_item))
+        });
+        if self == NanPolicy::Skip {
+            return ranked;
+        }
+        unranked.sort_unstable_by(|&(a_item, _), &(b_item, _)| tiebreaker(a_item, b_item));
+        if self == NanPolicy::Largest {
+            unranked.append(&mut ranked);
+            unranked
+        } else {
+            ranked.append(&mut unranked);
+            ranked
+        }
+    }
+}
+
+/// Splits `items` into those whose counts are comparable with every count, their own included,
+/// and the rest.
+#[allow(clippy::type_complexity)]
+fn split_unranked<'a, T, N>(
+    items: Vec<(&'a T, &'a N)>,
+) -> (Vec<(&'a T, &'a N)>, Vec<(&'a T, &'a N)>)
+where
+    N: PartialOrd,
+{
+    let (ranked, mut unranked): (Vec<_>, Vec<_>) =
+        items.into_iter().partition(|&(_, count)| !is_nan(count));
+    // The counts are usually totally ordered, in which case sorting them succeeds and shows it.
+    // Only otherwise is every pair of counts compared.
+    let counts = ranked.iter().map(|&(_, count)| count).collect::<Vec<_>>();
+    if merge_sorted(&counts).is_some() {
+        return (ranked, unranked);
+    }
+    let mut rankable = vec![true; counts.len()];
+    for i in 0..counts.len() {
+        for j in i + 1..counts.len() {
+            if counts[i].partial_cmp(counts[j]).is_none() {
+                rankable[i] = false;
+                rankable[j] = false;
+            }
+        }
+    }
+    let (ranked, incomparable): (Vec<_>, Vec<_>) = ranked
+        .into_iter()
+        .zip(rankable)
+        .partition(|&(_, rankable)| rankable);
+    unranked.extend(incomparable.into_iter().map(|(item, _)| item));
+    (ranked.into_iter().map(|(item, _)| item).collect(), unranked)
+}
+
+/// Merge sorts `counts` into increasing order, or returns `None` if that needs to compare two
+/// counts which are not comparable.
+///
+/// Each count in the result was found to be no greater than the next one, so when this
+/// succeeds every pair of counts is comparable.
+fn merge_sorted<'a, N: PartialOrd>(counts: &[&'a N]) -> Option<Vec<&'a N>> {
+    if counts.len() <= 1 {
+        return Some(counts.to_vec());
+    }
+    let (left, right) = counts.split_at(counts.len() / 2);
+    let (left, right) = (merge_sorted(left)?, merge_sorted(right)?);
+    let mut merged = Vec::with_capacity(counts.len());
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        if left[i].partial_cmp(right[j])? == Ordering::Greater {
+            merged.push(right[j]);
+            j += 1;
+        } else {
+            merged.push(left[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&left[i..]);
+    merged.extend_from_slice(&right[j..]);
+    Some(merged)
+}
+
+fn is_nan<N: PartialOrd>(count: &N) -> bool {
+    count.partial_cmp(count).is_none()
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Clone + PartialOrd,
+{
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common, for counts
+    /// which are only [`PartialOrd`].
+    ///
+    /// This is [`most_common()`] for counters such as `Counter<T, f64>`, placing NaN counts
+    /// according to `policy`.
+    ///
+    /// [`PartialOrd`]: https://doc.rust-lang.org/stable/std/cmp/trait.PartialOrd.html
+    /// [`most_common()`]: Counter::most_common
+    ///
+    /// ```rust
+    /// # use counter::{Counter, NanPolicy};
+    /// let weights = vec![('a', 0.5), ('b', f64::NAN), ('c', 2.5)]
+    ///     .into_iter()
+    ///     .collect::<Counter<_, f64>>();
+    /// let mc = weights.most_common_partial(NanPolicy::Skip);
+    /// assert_eq!(mc, vec![('c', 2.5), ('a', 0.5)]);
+    /// ```
+    ///
+    /// Note that the ordering of duplicates is unstable.
+    pub fn most_common_partial(&self, policy: NanPolicy) -> Vec<(T, N)> {
+        self.most_common_tiebreaker_partial(policy, |_a, _b| Ordering::Equal)
+    }
+
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common, for counts
+    /// which are only [`PartialOrd`].
+    ///
+    /// This is [`most_common_tiebreaker()`], placing NaN counts according to `policy`.
+    ///
+    /// [`PartialOrd`]: https://doc.rust-lang.org/stable/std/cmp/trait.PartialOrd.html
+    /// [`most_common_tiebreaker()`]: Counter::most_common_tiebreaker
+    ///
+    /// ```rust
+    /// # use counter::{Counter, NanPolicy};
+    /// let weights = vec![('a', 0.5), ('b', f64::NAN), ('c', 0.5)]
+    ///     .into_iter()
+    ///     .collect::<Counter<_, f64>>();
+    /// let mc = weights.most_common_tiebreaker_partial(NanPolicy::Smallest, |&a, &b| b.cmp(&a));
+    /// assert_eq!(mc[..2], [('c', 0.5), ('a', 0.5)]);
+    /// assert_eq!(mc[2].0, 'b');
+    /// ```
+    pub fn most_common_tiebreaker_partial<F>(&self, policy: NanPolicy, tiebreaker: F) -> Vec<(T, N)>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        policy
+            .rank(self.map.iter().collect(), tiebreaker)
+            .into_iter()
+            .map(|(key, count)| (key.clone(), count.clone()))
+            .collect()
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Clone + Ord,
+    N: Clone + PartialOrd,
+{
+    /// Create a vector of `(elem, frequency)` pairs, sorted most to least common, for counts
+    /// which are only [`PartialOrd`].
+    ///
+    /// This is [`most_common_ordered()`], placing NaN counts according to `policy`. Items with
+    /// the same count are sorted in increasing order of their keys.
+    ///
+    /// [`PartialOrd`]: https://doc.rust-lang.org/stable/std/cmp/trait.PartialOrd.html
+    /// [`most_common_ordered()`]: Counter::most_common_ordered
+    ///
+    /// ```rust
+    /// # use counter::{Counter, NanPolicy};
+    /// let weights = vec![('a', 0.5), ('b', f64::NAN), ('c', 0.5), ('d', 1.5)]
+    ///     .into_iter()
+    ///     .collect::<Counter<_, f64>>();
+    /// let keys = weights
+    ///     .most_common_ordered_partial(NanPolicy::Largest)
+    ///     .into_iter()
+    ///     .map(|(key, _)| key)
+    ///     .collect::<String>();
+    /// assert_eq!(keys, "bdac");
+    /// ```
+    pub fn most_common_ordered_partial(&self, policy: NanPolicy) -> Vec<(T, N)> {
+        self.most_common_tiebreaker_partial(policy, Ord::cmp)
+    }
+
+    /// Returns the `k` most common items in decreasing order of their counts, for counts which
+    /// are only [`PartialOrd`].
+    ///
+    /// The returned vector is the same as would be obtained by calling
+    /// [`most_common_ordered_partial()`] and then truncating the result to length `k`.
+    ///
+    /// [`PartialOrd`]: https://doc.rust-lang.org/stable/std/cmp/trait.PartialOrd.html
+    /// [`most_common_ordered_partial()`]: Counter::most_common_ordered_partial
+    ///
+    /// ```rust
+    /// # use counter::{Counter, NanPolicy};
+    /// let weights = vec![('a', 0.5), ('b', f64::NAN), ('c', 0.5), ('d', 1.5)]
+    ///     .into_iter()
+    ///     .collect::<Counter<_, f64>>();
+    /// let top2 = weights.k_most_common_ordered_partial(2, NanPolicy::Smallest);
+    /// assert_eq!(top2, vec![('d', 1.5), ('a', 0.5)]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n* \* log *n*), where *n* is the number of items in the counter, since every count
+    /// has to be checked for comparability. Only the `k` items returned are cloned.
+    pub fn k_most_common_ordered_partial(&self, k: usize, policy: NanPolicy) -> Vec<(T, N)> {
+        policy
+            .rank(self.map.iter().collect(), Ord::cmp)
+            .into_iter()
+            .take(k)
+            .map(|(key, count)| (key.clone(), count.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CountRank, NanPolicy};
+    use crate::Counter;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_k_most_common_with_ties() {
+        let counter = Counter::<_>::init("aaaaabbbcccdde".chars());
+        let with_ties = |k| {
+            counter
+                .k_most_common_with_ties(k)
+                .into_iter()
+                .map(|(item, _)| item)
+                .collect::<String>()
+        };
+        assert_eq!(with_ties(0), "");
+        assert_eq!(with_ties(1), "a");
+        assert_eq!(with_ties(2), "abc");
+        assert_eq!(with_ties(3), "abc");
+        assert_eq!(with_ties(4), "abcd");
+        assert_eq!(with_ties(5), "abcde");
+        assert_eq!(with_ties(10), "abcde");
+    }
+
+    #[test]
+    fn test_k_most_common_with_ties_extends_ordered() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let counter: Counter<_> = (0..200).map(|_| rng.gen_range(0..40)).collect();
+        let all = counter.most_common_ordered();
+        for k in 1..=counter.len() {
+            let with_ties = counter.k_most_common_with_ties(k);
+            assert_eq!(&with_ties[..k], &all[..k]);
+            let threshold = all[k - 1].1;
+            let expected = all.iter().filter(|(_, count)| *count >= threshold).count();
+            assert_eq!(with_ties.len(), expected);
+        }
+    }
+
+    #[test]
+    fn test_count_rank() {
+        let counter = Counter::<_>::init("aaaaabbbcccdde".chars());
+        let rank = |key| counter.count_rank(&key);
+        let expect = |dense, competition| Some(CountRank { dense, competition });
+        assert_eq!(rank('a'), expect(1, 1));
+        assert_eq!(rank('b'), expect(2, 2));
+        assert_eq!(rank('c'), expect(2, 2));
+        assert_eq!(rank('d'), expect(3, 4));
+        assert_eq!(rank('e'), expect(4, 5));
+        assert_eq!(rank('f'), None);
+    }
+
+    #[test]
+    fn test_partial_matches_ordered_for_integers() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let counter: Counter<_> = (0..200).map(|_| rng.gen_range(0..40)).collect();
+        let all = counter.most_common_ordered();
+        for &policy in &[NanPolicy::Smallest, NanPolicy::Largest, NanPolicy::Skip] {
+            assert_eq!(counter.most_common_ordered_partial(policy), all);
+            for k in 0..=counter.len() + 1 {
+                let topk = counter.k_most_common_ordered_partial(k, policy);
+                assert_eq!(&topk, &all[..k.min(all.len())]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nan_policy() {
+        let weights = vec![('a', 1.0), ('b', f64::NAN), ('c', -2.0), ('d', f64::NAN)]
+            .into_iter()
+            .collect::<Counter<_, f64>>();
+        let keys = |policy| {
+            weights
+                .most_common_ordered_partial(policy)
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<String>()
+        };
+        assert_eq!(keys(NanPolicy::Smallest), "acbd");
+        assert_eq!(keys(NanPolicy::Largest), "bdac");
+        assert_eq!(keys(NanPolicy::Skip), "ac");
+
+        let top = |k, policy| {
+            weights
+                .k_most_common_ordered_partial(k, policy)
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<String>()
+        };
+        assert_eq!(top(3, NanPolicy::Smallest), "acb");
+        assert_eq!(top(3, NanPolicy::Largest), "bda");
+        assert_eq!(top(3, NanPolicy::Skip), "ac");
+    }
+
+    /// Points ordered componentwise, so that `(2, 7)` and `(3, 3)` are incomparable.
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    struct Point(u32, u32);
+
+    impl PartialOrd for Point {
+        fn partial_cmp(&self, other: &Point) -> Option<Ordering> {
+            match (self.0.cmp(&other.0), self.1.cmp(&other.1)) {
+                (x, y) if x == y => Some(x),
+                (x, Ordering::Equal) | (Ordering::Equal, x) => Some(x),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_nan_policy_incomparable_counts() {
+        let mut points = Counter::<_, Point>::default();
+        points.insert('a', Point(1, 1));
+        points.insert('b', Point(2, 2));
+        points.insert('c', Point(3, 3));
+        points.insert('d', Point(2, 7));
+        points.insert('e', Point(0, 0));
+        points.insert('f', Point(2, 2));
+        let keys = |policy| {
+            points
+                .most_common_ordered_partial(policy)
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<String>()
+        };
+        // `c` and `d` are incomparable with each other, so neither can be ranked.
+        assert_eq!(keys(NanPolicy::Smallest), "bfaecd");
+        assert_eq!(keys(NanPolicy::Largest), "cdbfae");
+        assert_eq!(keys(NanPolicy::Skip), "bfae");
+        for &policy in &[NanPolicy::Smallest, NanPolicy::Largest, NanPolicy::Skip] {
+            let all = points.most_common_ordered_partial(policy);
+            for k in 0..=points.len() + 1 {
+                let topk = points.k_most_common_ordered_partial(k, policy);
+                assert_eq!(&topk, &all[..k.min(all.len())]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_most_common_partial_float_weights() {
+        let weights = vec![("x", 0.25f32), ("y", 4.0), ("z", 1.0)]
+            .into_iter()
+            .collect::<Counter<_, f32>>();
+        let mc = weights.most_common_partial(NanPolicy::Skip);
+        assert_eq!(mc, vec![("y", 4.0), ("z", 1.0), ("x", 0.25)]);
+    }
+}
@@ -0,0 +1,601 @@
+//! Arithmetic and set operators which borrow one or both of their operands.
+//!
+//! These behave exactly like the operators which take both counters by value, but clone the
+//! items and counts they need from borrowed counters instead of consuming them. When one operand
+//! is owned and the operation is commutative, the result reuses its storage.
+
+use crate::Counter;
+
+use num_traits::Zero;
+
+use std::hash::{BuildHasher, Hash};
+use std::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign,
+};
+
+impl<'a, T, N, S> AddAssign<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: AddAssign + Zero + Clone,
+    S: BuildHasher,
+{
+    /// Add a borrowed counter to this counter.
+    ///
+    /// `c += &d;` -> `c[x] += d[x]` for all `x`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// c += &d;
+    ///
+    /// let expect = [('a', 4), ('b', 3)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(c.into_map(), expect);
+    /// assert_eq!(d.len(), 2);
+    /// ```
+    fn add_assign(&mut self, rhs: &'a Counter<T, N, S>) {
+        for (key, value) in &rhs.map {
+            match self.map.get_mut(key) {
+                Some(entry) => *entry += value.clone(),
+                None => {
+                    self.map.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T, N, S> Add<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: AddAssign + Zero + Clone,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Add a borrowed counter to this counter, returning the sum.
+    fn add(mut self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T, N, S> Add<Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: AddAssign + Zero + Clone,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Add a counter to a borrowed counter, returning the sum.
+    fn add(self, mut rhs: Counter<T, N, S>) -> Self::Output {
+        rhs += self;
+        rhs
+    }
+}
+
+impl<'a, T, N, S> Add<&'a Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: AddAssign + Zero + Clone,
+    S: BuildHasher + Clone,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Add two borrowed counters, returning the sum.
+    ///
+    /// `out = &c + &d;` -> `out[x] == c[x] + d[x]` for all `x`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// let e = &c + &d;
+    ///
+    /// let expect = [('a', 4), ('b', 3)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(e.into_map(), expect);
+    /// ```
+    fn add(self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self.clone() + rhs
+    }
+}
+
+impl<'a, T, N, S> SubAssign<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + SubAssign + Zero + Clone,
+    S: BuildHasher,
+{
+    /// Subtract a borrowed counter (keeping only positive values).
+    ///
+    /// `c -= &d;` -> `c[x] -= d[x]` for all `x`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// c -= &d;
+    ///
+    /// let expect = [('a', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(c.into_map(), expect);
+    /// ```
+    fn sub_assign(&mut self, rhs: &'a Counter<T, N, S>) {
+        for (key, value) in &rhs.map {
+            let mut remove = false;
+            if let Some(entry) = self.map.get_mut(key) {
+                if *entry >= *value {
+                    *entry -= value.clone();
+                } else {
+                    remove = true;
+                }
+                if *entry == N::zero() {
+                    remove = true;
+                }
+            }
+            if remove {
+                self.map.remove(key);
+            }
+        }
+    }
+}
+
+impl<'a, T, N, S> Sub<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: PartialOrd + SubAssign + Zero + Clone,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Subtract a borrowed counter from this counter (keeping only positive values).
+    fn sub(mut self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<T, N, S> Sub<Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: PartialOrd + SubAssign + Zero + Clone,
+    S: BuildHasher + Clone,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Subtract a counter from a borrowed counter (keeping only positive values).
+    fn sub(self, rhs: Counter<T, N, S>) -> Self::Output {
+        self.clone() - rhs
+    }
+}
+
+impl<'a, T, N, S> Sub<&'a Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: PartialOrd + SubAssign + Zero + Clone,
+    S: BuildHasher + Clone,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Subtract two borrowed counters (keeping only positive values).
+    ///
+    /// `out = &c - &d;` -> `out[x] == c[x] - d[x]` for all `x`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// let e = &c - &d;
+    ///
+    /// let expect = [('a', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(e.into_map(), expect);
+    /// ```
+    fn sub(self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self.clone() - rhs
+    }
+}
+
+impl<'a, T, N, S> BitAndAssign<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Ord + Zero + Clone,
+    S: BuildHasher,
+{
+    /// Updates `self` with the intersection of `self` and a borrowed counter.
+    ///
+    /// `c &= &d;` -> `c[x] == min(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// c &= &d;
+    ///
+    /// let expect = [('a', 1), ('b', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(c.into_map(), expect);
+    /// ```
+    fn bitand_assign(&mut self, rhs: &'a Counter<T, N, S>) {
+        for (key, rhs_count) in &rhs.map {
+            if *rhs_count < self[key] {
+                self.map.insert(key.clone(), rhs_count.clone());
+            }
+        }
+    }
+}
+
+impl<'a, T, N, S> BitAnd<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero + Clone,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the intersection of `self` and a borrowed counter.
+    fn bitand(mut self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self.map.retain(|key, lhs_count| match rhs.map.get(key) {
+            Some(rhs_count) => {
+                if *rhs_count < *lhs_count {
+                    *lhs_count = rhs_count.clone();
+                }
+                true
+            }
+            None => false,
+        });
+        self
+    }
+}
+
+impl<T, N, S> BitAnd<Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: Ord + Zero + Clone,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the intersection of a borrowed counter and `rhs`.
+    fn bitand(self, rhs: Counter<T, N, S>) -> Self::Output {
+        rhs & self
+    }
+}
+
+impl<'a, T, N, S> BitAnd<&'a Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Ord + Zero + Clone,
+    S: BuildHasher + Clone,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the intersection of two borrowed counters.
+    ///
+    /// `out = &c & &d;` -> `out[x] == min(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// let e = &c & &d;
+    ///
+    /// let expect = [('a', 1), ('b', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(e.into_map(), expect);
+    /// ```
+    fn bitand(self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self.clone() & rhs
+    }
+}
+
+impl<'a, T, N, S> BitOrAssign<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Ord + Zero + Clone,
+    S: BuildHasher,
+{
+    /// Updates `self` with the union of `self` and a borrowed counter.
+    ///
+    /// `c |= &d;` -> `c[x] == max(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// c |= &d;
+    ///
+    /// let expect = [('a', 3), ('b', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(c.into_map(), expect);
+    /// ```
+    fn bitor_assign(&mut self, rhs: &'a Counter<T, N, S>) {
+        for (key, rhs_count) in &rhs.map {
+            match self.map.get_mut(key) {
+                Some(lhs_count) => {
+                    if *rhs_count > *lhs_count {
+                        *lhs_count = rhs_count.clone();
+                    }
+                }
+                None => {
+                    self.map.insert(key.clone(), rhs_count.clone());
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T, N, S> BitOr<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Ord + Zero + Clone,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the union of `self` and a borrowed counter.
+    fn bitor(mut self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl<T, N, S> BitOr<Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Ord + Zero + Clone,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the union of a borrowed counter and `rhs`.
+    fn bitor(self, mut rhs: Counter<T, N, S>) -> Self::Output {
+        rhs |= self;
+        rhs
+    }
+}
+
+impl<'a, T, N, S> BitOr<&'a Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: Ord + Zero + Clone,
+    S: BuildHasher + Clone,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the union of two borrowed counters.
+    ///
+    /// `out = &c | &d;` -> `out[x] == max(c[x], d[x])`
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abb".chars().collect::<Counter<_>>();
+    ///
+    /// let e = &c | &d;
+    ///
+    /// let expect = [('a', 3), ('b', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(e.into_map(), expect);
+    /// ```
+    fn bitor(self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self.clone() | rhs
+    }
+}
+
+impl<'a, T, N, S> BitXorAssign<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: PartialOrd + SubAssign + Zero + Clone,
+    S: BuildHasher,
+{
+    /// Updates `self` with the symmetric difference of `self` and a borrowed counter.
+    ///
+    /// `c ^= &d;` -> `c[x] == max(c[x], d[x]) - min(c[x], d[x])`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let mut c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abbc".chars().collect::<Counter<_>>();
+    ///
+    /// c ^= &d;
+    ///
+    /// let expect = [('a', 2), ('b', 1), ('c', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(c.into_map(), expect);
+    /// ```
+    fn bitxor_assign(&mut self, rhs: &'a Counter<T, N, S>) {
+        for (key, rhs_count) in &rhs.map {
+            match self.map.get_mut(key) {
+                Some(lhs_count) => {
+                    if *lhs_count >= *rhs_count {
+                        *lhs_count -= rhs_count.clone();
+                    } else {
+                        let lhs = std::mem::replace(lhs_count, rhs_count.clone());
+                        *lhs_count -= lhs;
+                    }
+                }
+                None => {
+                    self.map.insert(key.clone(), rhs_count.clone());
+                }
+            }
+        }
+        self.map.retain(|_, count| *count > N::zero());
+    }
+}
+
+impl<'a, T, N, S> BitXor<&'a Counter<T, N, S>> for Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: PartialOrd + SubAssign + Zero + Clone,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the symmetric difference of `self` and a borrowed counter.
+    fn bitxor(mut self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self ^= rhs;
+        self
+    }
+}
+
+impl<T, N, S> BitXor<Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: PartialOrd + SubAssign + Zero + Clone,
+    S: BuildHasher,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the symmetric difference of a borrowed counter and `rhs`.
+    fn bitxor(self, mut rhs: Counter<T, N, S>) -> Self::Output {
+        rhs ^= self;
+        rhs
+    }
+}
+
+impl<'a, T, N, S> BitXor<&'a Counter<T, N, S>> for &Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: PartialOrd + SubAssign + Zero + Clone,
+    S: BuildHasher + Clone,
+{
+    type Output = Counter<T, N, S>;
+
+    /// Returns the symmetric difference of two borrowed counters.
+    ///
+    /// `out = &c ^ &d;` -> `out[x] == max(c[x], d[x]) - min(c[x], d[x])`,
+    /// keeping only items with a value greater than [`N::zero()`].
+    ///
+    /// [`N::zero()`]:
+    /// https://docs.rs/num-traits/latest/num_traits/identities/trait.Zero.html#tymethod.zero
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let c = "aaab".chars().collect::<Counter<_>>();
+    /// let d = "abbc".chars().collect::<Counter<_>>();
+    ///
+    /// let e = &c ^ &d;
+    ///
+    /// let expect = [('a', 2), ('b', 1), ('c', 1)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(e.into_map(), expect);
+    /// ```
+    fn bitxor(self, rhs: &'a Counter<T, N, S>) -> Self::Output {
+        self.clone() ^ rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Counter;
+
+    /// Pairs of operands: 'a' is only on the left and 'd' only on the right, each side has the
+    /// larger count of one shared key, and either side may be empty.
+    fn operand_pairs() -> Vec<(Counter<char>, Counter<char>)> {
+        let c = Counter::<_>::init("aaabbc".chars());
+        let d = Counter::<_>::init("abbbccdd".chars());
+        vec![
+            (c.clone(), d.clone()),
+            (d.clone(), c.clone()),
+            (c.clone(), c.clone()),
+            (c, Counter::new()),
+            (Counter::new(), d),
+        ]
+    }
+
+    #[test]
+    fn test_reference_operators_match_owned() {
+        for (c, d) in operand_pairs() {
+            let sum = c.clone() + d.clone();
+            assert_eq!(&c + &d, sum);
+            assert_eq!(c.clone() + &d, sum);
+            assert_eq!(&c + d.clone(), sum);
+
+            let difference = c.clone() - d.clone();
+            assert_eq!(&c - &d, difference);
+            assert_eq!(c.clone() - &d, difference);
+            assert_eq!(&c - d.clone(), difference);
+
+            let intersection = c.clone() & d.clone();
+            assert_eq!(&c & &d, intersection);
+            assert_eq!(c.clone() & &d, intersection);
+            assert_eq!(&c & d.clone(), intersection);
+
+            let union = c.clone() | d.clone();
+            assert_eq!(&c | &d, union);
+            assert_eq!(c.clone() | &d, union);
+            assert_eq!(&c | d.clone(), union);
+
+            let symmetric_difference = c.clone() ^ d.clone();
+            assert_eq!(&c ^ &d, symmetric_difference);
+            assert_eq!(c.clone() ^ &d, symmetric_difference);
+            assert_eq!(&c ^ d.clone(), symmetric_difference);
+        }
+    }
+
+    #[test]
+    fn test_reference_assign_operators_match_owned() {
+        for (c, d) in operand_pairs() {
+            let mut owned = c.clone();
+            let mut borrowed = c.clone();
+            owned += d.clone();
+            borrowed += &d;
+            assert_eq!(borrowed, owned);
+
+            owned -= d.clone();
+            borrowed -= &d;
+            assert_eq!(borrowed, owned);
+
+            owned |= d.clone();
+            borrowed |= &d;
+            assert_eq!(borrowed, owned);
+
+            owned ^= d.clone();
+            borrowed ^= &d;
+            assert_eq!(borrowed, owned);
+
+            owned &= d.clone();
+            borrowed &= &d;
+            assert_eq!(borrowed, owned);
+            let mut owned = c.clone();
+            let mut borrowed = c;
+            owned &= d.clone();
+            borrowed &= &d;
+            assert_eq!(borrowed, owned);
+        }
+    }
+
+    #[test]
+    fn test_merge_shared_counter_into_many() {
+        let shared = Counter::<_>::init("abc".chars());
+        let mut accumulators = vec![Counter::<_>::init("a".chars()), Counter::new()];
+        for accumulator in &mut accumulators {
+            *accumulator += &shared;
+        }
+        assert_eq!(accumulators[0], Counter::init("aabc".chars()));
+        assert_eq!(accumulators[1], shared);
+    }
+}
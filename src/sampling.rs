@@ -0,0 +1,235 @@
+//! Weighted random sampling, enabled with the `rand` feature.
+
+use crate::Counter;
+
+use num_traits::{One, ToPrimitive, Zero};
+use rand::distributions::Distribution;
+use rand::Rng;
+
+use std::hash::{BuildHasher, Hash};
+use std::ops::SubAssign;
+
+/// Draws the items of a counter at random, with probability proportional to their counts.
+///
+/// The sampler is built once, in time linear in the number of items, using [Vose's alias
+/// method]; each draw then takes constant time. Items are drawn with replacement, and the counter
+/// is borrowed for as long as the sampler lives.
+///
+/// This `struct` is created by [`Counter::sampler`]. Draw from it through its implementation of
+/// [`rand::distributions::Distribution`].
+///
+/// [Vose's alias method]: https://www.keithschwarz.com/darts-dice-coins/
+/// [`rand::distributions::Distribution`]: https://docs.rs/rand/0.8/rand/distributions/trait.Distribution.html
+#[derive(Clone, Debug)]
+pub struct Sampler<'a, T> {
+    items: Vec<&'a T>,
+    // For each column, the probability of keeping its own item rather than its alias.
+    keep: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl<'a, T> Distribution<&'a T> for Sampler<'a, T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> &'a T {
+        let column = rng.gen_range(0..self.items.len());
+        if rng.gen::<f64>() < self.keep[column] {
+            self.items[column]
+        } else {
+            self.items[self.alias[column]]
+        }
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: ToPrimitive,
+{
+    /// Create a [`Sampler`] which draws items with probability proportional to their counts.
+    ///
+    /// Items whose count is not positive are never drawn. Returns `None` if there are no items
+    /// with a positive count.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// use rand::distributions::Distribution;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let counter = "aaab".chars().collect::<Counter<_>>();
+    /// let sampler = counter.sampler().unwrap();
+    /// let draws = sampler
+    ///     .sample_iter(StdRng::seed_from_u64(0))
+    ///     .take(1000)
+    ///     .collect::<Counter<_>>();
+    /// assert!(draws[&&'a'] > draws[&&'b']);
+    /// ```
+    pub fn sampler(&self) -> Option<Sampler<'_, T>> {
+        let (counts, total) = self.positive_counts();
+        let (items, weights): (Vec<_>, Vec<_>) = counts.unzip();
+        if items.is_empty() {
+            return None;
+        }
+
+        // Scale the weights so that they average one, then pair each column which falls short
+        // of one with a column which has more than enough to fill it.
+        let len = items.len();
+        let mut keep = weights
+            .into_iter()
+            .map(|weight| weight * len as f64 / total)
+            .collect::<Vec<_>>();
+        let mut alias = (0..len).collect::<Vec<_>>();
+        let (mut small, mut large): (Vec<_>, Vec<_>) = (0..len).partition(|&i| keep[i] < 1.0);
+        while let (Some(&short), Some(&tall)) = (small.last(), large.last()) {
+            small.pop();
+            alias[short] = tall;
+            keep[tall] -= 1.0 - keep[short];
+            if keep[tall] < 1.0 {
+                large.pop();
+                small.push(tall);
+            }
+        }
+        // Whatever is left over is one, up to rounding errors.
+        for column in small.into_iter().chain(large) {
+            keep[column] = 1.0;
+        }
+
+        Some(Sampler { items, keep, alias })
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq + Clone,
+    N: ToPrimitive + PartialOrd + SubAssign + Zero + One,
+    S: BuildHasher,
+{
+    /// Draw up to `amount` items at random without replacement, with probability proportional to
+    /// their counts, removing each one from the counter as it is drawn.
+    ///
+    /// Like [`subtract()`], each draw lowers the count of the drawn item by one, and removes it
+    /// once its count is no longer positive. Fewer than `amount` items are returned if the
+    /// counter runs out.
+    ///
+    /// Each draw scans every item of the counter, so it takes *O*(*n*) time for a counter of *n*
+    /// distinct items, and drawing `amount` items takes *O*(`amount` · *n*). To draw many items
+    /// with replacement, use [`sampler()`] instead.
+    ///
+    /// [`subtract()`]: Counter::subtract
+    /// [`sampler()`]: Counter::sampler
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let mut counter = "aaab".chars().collect::<Counter<_>>();
+    /// let mut drawn = counter.sample_without_replacement(&mut rng, 3);
+    /// assert_eq!(drawn.len(), 3);
+    /// assert_eq!(counter.values().sum::<usize>(), 1);
+    ///
+    /// drawn.extend(counter.sample_without_replacement(&mut rng, 3));
+    /// drawn.sort();
+    /// assert_eq!(drawn, vec!['a', 'a', 'a', 'b']);
+    /// assert!(counter.is_empty());
+    /// ```
+    pub fn sample_without_replacement<R>(&mut self, rng: &mut R, amount: usize) -> Vec<T>
+    where
+        R: Rng + ?Sized,
+    {
+        let mut drawn = Vec::with_capacity(amount);
+        while drawn.len() < amount {
+            let (counts, total) = self.positive_counts();
+            if total <= 0.0 {
+                break;
+            }
+            let mut target = rng.gen_range(0.0..total);
+            let mut choice = None;
+            for (item, count) in counts {
+                choice = Some(item);
+                if target < count {
+                    break;
+                }
+                target -= count;
+            }
+            let item = choice
+                .expect("there is an item with a positive count")
+                .clone();
+
+            let count = self.map.get_mut(&item).expect("the item was just drawn");
+            *count -= N::one();
+            if *count <= N::zero() {
+                self.map.remove(&item);
+            }
+            drawn.push(item);
+        }
+        drawn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Counter;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_sampler_frequencies() {
+        let counter = vec![('a', 1), ('b', 2), ('c', 3), ('d', 0), ('e', 10)]
+            .into_iter()
+            .collect::<Counter<char>>();
+        let sampler = counter.sampler().unwrap();
+        let draws = sampler
+            .sample_iter(StdRng::seed_from_u64(1))
+            .take(160_000)
+            .collect::<Counter<_>>();
+        assert_eq!(draws.get(&&'d'), None);
+        for (item, &count) in counter.iter().filter(|(_, &count)| count > 0) {
+            let expected = 10_000.0 * count as f64;
+            let error = (draws[&item] as f64 - expected).abs() / expected;
+            assert!(error < 0.05, "{} for {}", error, item);
+        }
+    }
+
+    #[test]
+    fn test_sampler_single_item() {
+        let counter = Counter::<_>::init("zzz".chars());
+        let sampler = counter.sampler().unwrap();
+        let mut rng = StdRng::seed_from_u64(2);
+        assert!((0..100).all(|_| *sampler.sample(&mut rng) == 'z'));
+    }
+
+    #[test]
+    fn test_sampler_empty() {
+        assert!(Counter::<char>::new().sampler().is_none());
+        let zeros = vec![('a', 0)].into_iter().collect::<Counter<char, i32>>();
+        assert!(zeros.sampler().is_none());
+    }
+
+    #[test]
+    fn test_sample_without_replacement() {
+        let original = vec![('a', 50), ('b', 1), ('c', 3)]
+            .into_iter()
+            .collect::<Counter<char>>();
+        let mut counter = original.clone();
+        let mut rng = StdRng::seed_from_u64(3);
+
+        assert!(counter.sample_without_replacement(&mut rng, 0).is_empty());
+        assert_eq!(counter, original);
+
+        let first = counter.sample_without_replacement(&mut rng, 10);
+        assert_eq!(first.len(), 10);
+        assert_eq!(counter.values().sum::<usize>(), 44);
+
+        // Once the counter runs out, fewer items than asked for are returned.
+        let rest = counter.sample_without_replacement(&mut rng, 100);
+        assert_eq!(rest.len(), 44);
+        assert!(counter.is_empty());
+        assert!(counter.sample_without_replacement(&mut rng, 1).is_empty());
+        assert_eq!(
+            first.into_iter().chain(rest).collect::<Counter<_>>(),
+            original
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Optional [`serde`] support for [`Counter`], enabled with the `serde` feature.
+//!
+//! A `Counter<T, N>` is serialized exactly like the [`HashMap`] it wraps, so it round-trips
+//! through any format which can represent a map.
+//!
+//! [`serde`]: https://docs.rs/serde
+//! [`HashMap`]: https://doc.rust-lang.org/stable/std/collections/struct.HashMap.html
+
+use crate::Counter;
+
+use num_traits::Zero;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+impl<T, N, S> Serialize for Counter<T, N, S>
+where
+    T: Serialize + Hash + Eq,
+    N: Serialize,
+    S: BuildHasher,
+{
+    /// Serialize the counter as a map from items to their counts.
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        self.map.serialize(serializer)
+    }
+}
+
+impl<'de, T, N, S> Deserialize<'de> for Counter<T, N, S>
+where
+    T: Deserialize<'de> + Hash + Eq,
+    N: Deserialize<'de> + PartialOrd + Zero,
+    S: BuildHasher + Default,
+{
+    /// Deserialize a counter from a map of items to their counts.
+    ///
+    /// Just as with [`subtract`], non-positive counts are automatically removed.
+    ///
+    /// [`subtract`]: Counter::subtract
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// # use std::collections::HashMap;
+    /// let counter: Counter<char, i32> = serde_json::from_str(r#"{"a": 2, "b": 0, "c": -1}"#)
+    ///     .unwrap();
+    /// let expect = [('a', 2)].iter().cloned().collect::<HashMap<_, _>>();
+    /// assert_eq!(counter.into_map(), expect);
+    /// ```
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut map = HashMap::<T, N, S>::deserialize(deserializer)?;
+        map.retain(|_, count| *count > N::zero());
+        Ok(Counter {
+            map,
+            zero: N::zero(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Counter;
+    use maplit::hashmap;
+
+    #[test]
+    fn test_serialize() {
+        let counter = "abbccc".chars().collect::<Counter<_>>();
+        let json = serde_json::to_string(&counter).unwrap();
+        let map: std::collections::HashMap<char, usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(map, counter.into_map());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let counter = "able babble table babble"
+            .split_whitespace()
+            .collect::<Counter<_>>();
+        let json = serde_json::to_string(&counter).unwrap();
+        let loaded: Counter<&str> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, counter);
+    }
+
+    #[test]
+    fn test_deserialize_drops_non_positive() {
+        let counter: Counter<String, i8> =
+            serde_json::from_str(r#"{"a": 1, "b": 0, "c": -3, "d": 4}"#).unwrap();
+        let expected = hashmap! {
+            "a".to_string() => 1,
+            "d".to_string() => 4,
+        };
+        assert_eq!(counter.map, expected);
+    }
+}
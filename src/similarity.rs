@@ -0,0 +1,389 @@
+//! Similarities and distances between counters, treated as vectors indexed by their items.
+//!
+//! None of these clone either counter. Items missing from a counter count as zero, and counts
+//! are converted to `f64` to compute the results.
+
+use crate::Counter;
+
+use num_traits::ToPrimitive;
+
+use std::hash::{BuildHasher, Hash};
+
+/// Converts a count to `f64`, treating unrepresentable counts as zero.
+fn to_f64<N: ToPrimitive>(count: &N) -> f64 {
+    count.to_f64().unwrap_or(0.0)
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: ToPrimitive,
+{
+    /// Returns the sum of the absolute values of the counts.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "aaab".chars().collect::<Counter<_>>();
+    /// assert_eq!(counter.l1_norm(), 4.0);
+    /// ```
+    pub fn l1_norm(&self) -> f64 {
+        self.map.values().map(|count| to_f64(count).abs()).sum()
+    }
+
+    /// Returns the Euclidean length of the counts.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "aaabbbb".chars().collect::<Counter<_>>();
+    /// assert_eq!(counter.l2_norm(), 5.0);
+    /// ```
+    pub fn l2_norm(&self) -> f64 {
+        self.map
+            .values()
+            .map(|count| to_f64(count).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Returns the largest absolute value of the counts, or zero for an empty counter.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let counter = "aaab".chars().collect::<Counter<_>>();
+    /// assert_eq!(counter.linf_norm(), 3.0);
+    /// ```
+    pub fn linf_norm(&self) -> f64 {
+        self.map
+            .values()
+            .map(|count| to_f64(count).abs())
+            .fold(0.0, f64::max)
+    }
+}
+
+impl<T, N, S> Counter<T, N, S>
+where
+    T: Hash + Eq,
+    N: ToPrimitive,
+    S: BuildHasher,
+{
+    /// Returns the count of `key` as an `f64`, or zero if it is missing.
+    fn count_f64(&self, key: &T) -> f64 {
+        self.map.get(key).map_or(0.0, to_f64)
+    }
+
+    /// Returns the counts of every item in either counter, as `(self[x], other[x])` pairs.
+    fn paired_counts<'a>(
+        &'a self,
+        other: &'a Counter<T, N, S>,
+    ) -> impl Iterator<Item = (f64, f64)> + 'a {
+        let ours = self
+            .map
+            .iter()
+            .map(move |(key, count)| (to_f64(count), other.count_f64(key)));
+        let theirs = other
+            .map
+            .iter()
+            .filter(move |(key, _)| !self.map.contains_key(*key))
+            .map(|(_, count)| (0.0, to_f64(count)));
+        ours.chain(theirs)
+    }
+
+    /// Returns the dot product of the counts of `self` and `other`.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let a = "aaab".chars().collect::<Counter<_>>();
+    /// let b = "abbc".chars().collect::<Counter<_>>();
+    /// assert_eq!(a.dot(&b), 5.0);
+    /// ```
+    pub fn dot(&self, other: &Counter<T, N, S>) -> f64 {
+        let (small, large) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        small
+            .map
+            .iter()
+            .map(|(key, count)| to_f64(count) * large.count_f64(key))
+            .sum()
+    }
+
+    /// Returns the [cosine similarity] of `self` and `other`.
+    ///
+    /// This is zero if either counter has no non-zero counts.
+    ///
+    /// [cosine similarity]: https://en.wikipedia.org/wiki/Cosine_similarity
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let a = "aab".chars().collect::<Counter<_>>();
+    /// let b = "aaaabb".chars().collect::<Counter<_>>();
+    /// assert!((a.cosine_similarity(&b) - 1.0).abs() < 1e-12);
+    /// let c = "cd".chars().collect::<Counter<_>>();
+    /// assert_eq!(a.cosine_similarity(&c), 0.0);
+    /// ```
+    pub fn cosine_similarity(&self, other: &Counter<T, N, S>) -> f64 {
+        let norms = self.l2_norm() * other.l2_norm();
+        if norms == 0.0 {
+            0.0
+        } else {
+            self.dot(other) / norms
+        }
+    }
+
+    /// Returns the sum of the absolute differences between the counts of `self` and `other`.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let a = "aaab".chars().collect::<Counter<_>>();
+    /// let b = "abbc".chars().collect::<Counter<_>>();
+    /// assert_eq!(a.l1_distance(&b), 4.0);
+    /// ```
+    pub fn l1_distance(&self, other: &Counter<T, N, S>) -> f64 {
+        self.paired_counts(other).map(|(a, b)| (a - b).abs()).sum()
+    }
+
+    /// Returns the Euclidean distance between the counts of `self` and `other`.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let a = "aaab".chars().collect::<Counter<_>>();
+    /// let b = "abbc".chars().collect::<Counter<_>>();
+    /// assert_eq!(a.l2_distance(&b), 6f64.sqrt());
+    /// ```
+    pub fn l2_distance(&self, other: &Counter<T, N, S>) -> f64 {
+        self.paired_counts(other)
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Returns the largest absolute difference between the counts of `self` and `other`.
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let a = "aaab".chars().collect::<Counter<_>>();
+    /// let b = "abbc".chars().collect::<Counter<_>>();
+    /// assert_eq!(a.linf_distance(&b), 2.0);
+    /// ```
+    pub fn linf_distance(&self, other: &Counter<T, N, S>) -> f64 {
+        self.paired_counts(other)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the weighted [Jaccard similarity] of `self` and `other`: the total of their
+    /// intersection divided by the total of their union.
+    ///
+    /// This is the same as `(a & b).total() / (a | b).total()`, without building either counter.
+    /// Two empty counters have a similarity of one.
+    ///
+    /// [Jaccard similarity]: https://en.wikipedia.org/wiki/Jaccard_index#Weighted_Jaccard_similarity_and_distance
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let a = "aaab".chars().collect::<Counter<_>>();
+    /// let b = "abbc".chars().collect::<Counter<_>>();
+    /// assert_eq!(a.weighted_jaccard(&b), 2.0 / 6.0);
+    /// ```
+    pub fn weighted_jaccard(&self, other: &Counter<T, N, S>) -> f64 {
+        let (intersection, union) = self
+            .paired_counts(other)
+            .fold((0.0, 0.0), |(intersection, union), (a, b)| {
+                (intersection + a.min(b), union + a.max(b))
+            });
+        if union == 0.0 {
+            1.0
+        } else {
+            intersection / union
+        }
+    }
+
+    /// Returns the [Bray-Curtis dissimilarity] of `self` and `other`: the sum of the absolute
+    /// differences of their counts, divided by the sum of all their counts.
+    ///
+    /// This lies between zero, for identical counters, and one, for counters with no items in
+    /// common. Two empty counters have a dissimilarity of zero.
+    ///
+    /// [Bray-Curtis dissimilarity]: https://en.wikipedia.org/wiki/Bray%E2%80%93Curtis_dissimilarity
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let a = "aaab".chars().collect::<Counter<_>>();
+    /// let b = "abbc".chars().collect::<Counter<_>>();
+    /// assert_eq!(a.bray_curtis(&b), 0.5);
+    /// ```
+    pub fn bray_curtis(&self, other: &Counter<T, N, S>) -> f64 {
+        let (difference, sum) = self
+            .paired_counts(other)
+            .fold((0.0, 0.0), |(difference, sum), (a, b)| {
+                (difference + (a - b).abs(), sum + a + b)
+            });
+        if sum == 0.0 {
+            0.0
+        } else {
+            difference / sum
+        }
+    }
+
+    /// Returns the [Hellinger distance] between the distributions described by `self` and
+    /// `other`, which give each item its share of their total count.
+    ///
+    /// This lies between zero, for proportional counters, and one, for counters with no items in
+    /// common. An empty counter is at distance one from any other counter except an empty one.
+    ///
+    /// [Hellinger distance]: https://en.wikipedia.org/wiki/Hellinger_distance
+    ///
+    /// ```rust
+    /// # use counter::Counter;
+    /// let a = "aab".chars().collect::<Counter<_>>();
+    /// let b = "aaaabb".chars().collect::<Counter<_>>();
+    /// assert!(a.hellinger(&b).abs() < 1e-7);
+    /// let c = "cd".chars().collect::<Counter<_>>();
+    /// assert_eq!(a.hellinger(&c), 1.0);
+    /// ```
+    pub fn hellinger(&self, other: &Counter<T, N, S>) -> f64 {
+        let (_, total) = self.positive_counts();
+        let (_, other_total) = other.positive_counts();
+        if total == 0.0 || other_total == 0.0 {
+            return if total == other_total { 0.0 } else { 1.0 };
+        }
+        let coefficient = self
+            .paired_counts(other)
+            .filter(|&(a, b)| a > 0.0 && b > 0.0)
+            .map(|(a, b)| (a / total * b / other_total).sqrt())
+            .sum::<f64>();
+        (1.0 - coefficient).max(0.0).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Counter;
+
+    const EPSILON: f64 = 1e-9;
+
+    /// Two counters which share some items and not others.
+    fn overlapping() -> (Counter<char>, Counter<char>) {
+        (
+            vec![('a', 3), ('b', 1), ('c', 2)].into_iter().collect(),
+            vec![('a', 1), ('b', 4), ('d', 2)].into_iter().collect(),
+        )
+    }
+
+    #[test]
+    fn test_jaccard() {
+        let (a, b) = overlapping();
+        assert_eq!(a.weighted_jaccard(&b), 2.0 / 11.0);
+        let expected = (a.clone() & b.clone()).total::<usize>() as f64
+            / (a.clone() | b.clone()).total::<usize>() as f64;
+        assert!((a.weighted_jaccard(&b) - expected).abs() < EPSILON);
+        assert_eq!(a.weighted_jaccard(&a), 1.0);
+
+        let disjoint = Counter::<_>::init("xyz".chars());
+        assert_eq!(a.weighted_jaccard(&disjoint), 0.0);
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = overlapping();
+        assert_eq!(a.l1_distance(&b), 9.0);
+        assert_eq!(a.l2_distance(&b), 21f64.sqrt());
+        assert_eq!(a.linf_distance(&b), 3.0);
+        assert_eq!(a.l1_distance(&b), b.l1_distance(&a));
+        assert_eq!(a.l1_distance(&a), 0.0);
+        assert_eq!(a.bray_curtis(&b), 9.0 / 13.0);
+
+        let difference = a.clone() ^ b.clone();
+        assert_eq!(a.l1_distance(&b), difference.l1_norm());
+        assert!((a.l2_distance(&b) - difference.l2_norm()).abs() < EPSILON);
+        assert_eq!(a.linf_distance(&b), difference.linf_norm());
+
+        let disjoint = Counter::<_>::init("xyz".chars());
+        assert_eq!(a.bray_curtis(&disjoint), 1.0);
+        assert_eq!(a.l1_distance(&disjoint), 9.0);
+    }
+
+    #[test]
+    fn test_distances_with_zero_and_negative_counts() {
+        let a = vec![('x', 3), ('y', -2), ('z', 0)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        let b = vec![('x', 1), ('w', 4)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        assert_eq!(a.l1_distance(&b), 8.0);
+        assert_eq!(a.l2_distance(&b), 24f64.sqrt());
+        assert_eq!(a.linf_distance(&b), 4.0);
+        assert_eq!(b.linf_distance(&a), 4.0);
+    }
+
+    #[test]
+    fn test_dot_and_cosine() {
+        let (a, b) = overlapping();
+        assert_eq!(a.dot(&b), 7.0);
+        assert_eq!(a.dot(&b), b.dot(&a));
+        assert!((a.cosine_similarity(&b) - 7.0 / (14f64 * 21.0).sqrt()).abs() < EPSILON);
+        assert!((a.cosine_similarity(&a) - 1.0).abs() < EPSILON);
+
+        let disjoint = Counter::<_>::init("xyz".chars());
+        assert_eq!(a.dot(&disjoint), 0.0);
+        assert_eq!(a.cosine_similarity(&disjoint), 0.0);
+
+        let signed = vec![('a', 1), ('b', -1)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        let positive = vec![('a', 1), ('b', 1)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        let zeros = vec![('a', 0)].into_iter().collect::<Counter<char, i32>>();
+        assert_eq!(signed.cosine_similarity(&positive), 0.0);
+        assert_eq!(zeros.cosine_similarity(&positive), 0.0);
+    }
+
+    #[test]
+    fn test_hellinger() {
+        let (a, b) = overlapping();
+        let coefficient = (3f64.sqrt() + 2.0) / 42f64.sqrt();
+        assert!((a.hellinger(&b) - (1.0 - coefficient).sqrt()).abs() < EPSILON);
+        assert!((a.hellinger(&b) - b.hellinger(&a)).abs() < EPSILON);
+        assert!(a.hellinger(&a) < 1e-7);
+
+        let disjoint = Counter::<_>::init("xyz".chars());
+        assert_eq!(a.hellinger(&disjoint), 1.0);
+
+        // Only positive counts take part.
+        let signed = vec![('a', 2), ('b', -5)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        let single = vec![('a', 1)].into_iter().collect::<Counter<char, i32>>();
+        assert!(signed.hellinger(&single) < 1e-7);
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty = Counter::<char>::new();
+        let full = Counter::<_>::init("abc".chars());
+        assert_eq!(empty.l2_norm(), 0.0);
+        assert_eq!(empty.linf_norm(), 0.0);
+        assert_eq!(empty.weighted_jaccard(&empty), 1.0);
+        assert_eq!(empty.weighted_jaccard(&full), 0.0);
+        assert_eq!(empty.cosine_similarity(&full), 0.0);
+        assert_eq!(empty.bray_curtis(&empty), 0.0);
+        assert_eq!(empty.bray_curtis(&full), 1.0);
+        assert_eq!(empty.hellinger(&empty), 0.0);
+        assert_eq!(empty.hellinger(&full), 1.0);
+        assert_eq!(empty.l1_distance(&full), 3.0);
+    }
+
+    #[test]
+    fn test_signed_norms() {
+        let counter = vec![('a', 3), ('b', -4)]
+            .into_iter()
+            .collect::<Counter<char, i32>>();
+        assert_eq!(counter.l1_norm(), 7.0);
+        assert_eq!(counter.l2_norm(), 5.0);
+        assert_eq!(counter.linf_norm(), 4.0);
+    }
+}